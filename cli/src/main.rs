@@ -1,24 +1,38 @@
+mod players;
 mod renderer;
+use players::*;
 use renderer::*;
 
 use once_cell::sync::Lazy;
 
 static IS_TESTING: Lazy<bool> = Lazy::new(|| {
-    match || -> anyhow::Result<bool> {
+    (|| -> anyhow::Result<bool> {
         let val = std::env::var("TESTING")?;
         Ok(val == "1")
-    }() {
-        Ok(val) => val,
-        Err(_) => false,
-    }
+    })()
+    .unwrap_or_default()
 });
 
-fn main() {
+fn main() -> anyhow::Result<()> {
+    // note: an invalid `START_POSITION` or `START_PLAYER` fails here, see `game::State::initial`
+    game::State::initial()?;
+
+    let p1_kind = crate::player_kind(game::Player::P1)?;
+    let p2_kind = crate::player_kind(game::Player::P2)?;
+    let mut p1_policy = crate::new_policy(game::Player::P1)?;
+    let mut p2_policy = crate::new_policy(game::Player::P2)?;
+
     let mut state = game::State::new();
     crate::print_state(&state);
 
     loop {
-        let action = read_action(&state);
+        let (action, kind) = match state.player_to_act {
+            game::Player::P1 => (p1_policy.choose(&state), p1_kind),
+            game::Player::P2 => (p2_policy.choose(&state), p2_kind),
+        };
+        if kind != crate::PlayerKind::Human {
+            crate::print_action(&action, &state);
+        }
         let (next_state, result) = state.step(&action);
         crate::print_state(&next_state);
         crate::print_result(&result, &state);
@@ -27,4 +41,5 @@ fn main() {
             _ => break,
         }
     }
+    Ok(())
 }
@@ -0,0 +1,68 @@
+use anyhow::Context;
+use once_cell::sync::Lazy;
+
+/// who picks the actions of a player
+#[derive(
+    Default,
+    // sane defaults for value objects:
+    Clone,
+    Copy,
+    Debug,
+    // hashable:
+    PartialEq,
+    Eq,
+    Hash,
+)]
+pub enum PlayerKind {
+    #[default]
+    Human, // see `crate::HumanPolicy`
+    Bot(solver::Bot),
+}
+
+/// `human` or any `solver::Bot`, e.g. `greedy` or `mcts:100`
+impl std::str::FromStr for PlayerKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<PlayerKind> {
+        match s {
+            "human" => Ok(PlayerKind::Human),
+            _ => Ok(PlayerKind::Bot(s.parse::<solver::Bot>()?)),
+        }
+    }
+}
+
+/// the kind of `player`, configurable via env `PLAYER1` or `PLAYER2` (a human by default)
+pub fn player_kind(player: game::Player) -> anyhow::Result<PlayerKind> {
+    let key = match player {
+        game::Player::P1 => "PLAYER1",
+        game::Player::P2 => "PLAYER2",
+    };
+    match std::env::var(key) {
+        Ok(val) => val
+            .parse::<PlayerKind>()
+            .with_context(|| format!("invalid {key} `{val}`")),
+        Err(_) => Ok(PlayerKind::default()),
+    }
+}
+
+/// configurable via env `PLAYER_SEED`
+pub static PLAYER_SEED: Lazy<u64> = Lazy::new(|| match std::env::var("PLAYER_SEED") {
+    Ok(val) => val
+        .parse::<u64>()
+        .unwrap_or_else(|err| panic!("invalid PLAYER_SEED `{val}`: {err}")),
+    Err(_) => 0,
+});
+
+/// the policy of `player`, configured via env `PLAYER1` or `PLAYER2`
+///
+/// note: player 2 is seeded with `PLAYER_SEED + 1` so that two random players don't mirror each other
+pub fn new_policy(player: game::Player) -> anyhow::Result<Box<dyn game::Policy>> {
+    let seed = match player {
+        game::Player::P1 => *PLAYER_SEED,
+        game::Player::P2 => PLAYER_SEED.wrapping_add(1),
+    };
+    match player_kind(player)? {
+        PlayerKind::Human => Ok(Box::new(crate::HumanPolicy)),
+        PlayerKind::Bot(bot) => bot.new_policy(seed),
+    }
+}
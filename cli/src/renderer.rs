@@ -2,7 +2,7 @@ use anyhow::Context;
 use once_cell::sync::Lazy;
 use std::io::Write;
 
-static LINE: Lazy<String> = Lazy::new(|| "-".repeat(*game::BOARD_SIZE * 4 + 1));
+static LINE: Lazy<String> = Lazy::new(|| "-".repeat(game::BOARD.cols * 4 + 1));
 
 pub fn read_action(state: &game::State) -> game::Action {
     loop {
@@ -10,12 +10,16 @@ pub fn read_action(state: &game::State) -> game::Action {
             if !*crate::IS_TESTING {
                 {
                     print!(
-                        "Player {} action? {{",
+                        "Player {} action",
                         match state.player_to_act {
                             game::Player::P1 => "1",
                             game::Player::P2 => "2",
                         }
                     );
+                    if game::TURN_SCHEDULE.iter().any(|&n| n > 1) {
+                        print!(" (stones left: {})", state.stones_left);
+                    }
+                    print!("? {{");
                     for (i, act) in state.all_actions().iter().enumerate() {
                         if i != 0 {
                             print!(", ");
@@ -32,12 +36,9 @@ pub fn read_action(state: &game::State) -> game::Action {
                 .read_line(&mut buf)
                 .context("failed stdin.read_line")?;
 
-            let parsed = buf
-                .trim()
-                .parse::<usize>()
-                .context("you should enter a nonnegative integer!")?;
+            let parsed = buf.trim().parse::<game::Action>()?;
 
-            game::Action::new(state, parsed)
+            parsed.validate(state)
         }() {
             Ok(val) => break val,
             Err(err) => println!("{err}"),
@@ -45,26 +46,70 @@ pub fn read_action(state: &game::State) -> game::Action {
     }
 }
 
+/// the actions read from stdin, see `read_action`
+pub struct HumanPolicy;
+
+impl game::Policy for HumanPolicy {
+    fn choose(&mut self, state: &game::State) -> game::Action {
+        read_action(state)
+    }
+}
+
+/// the action chosen by a bot, since no prompt shows whose turn it is
+pub fn print_action(action: &game::Action, orig_state: &game::State) {
+    if *crate::IS_TESTING {
+        return;
+    }
+
+    println!(
+        "Player {} plays {action}",
+        match orig_state.player_to_act {
+            game::Player::P1 => "1",
+            game::Player::P2 => "2",
+        }
+    );
+}
+
 pub fn print_state(state: &game::State) {
     if *crate::IS_TESTING {
         return;
     }
 
+    let is_grid = game::BOARD.rows > 1;
+    // row labels are only needed on a grid
+    let margin = if is_grid {
+        " ".repeat(game::BOARD.rows.to_string().len() + 1)
+    } else {
+        String::new()
+    };
+
     println!();
-    println!("{}", *LINE);
-    print!("|");
-    for cell in &state.board {
-        print!(
-            " {} |",
-            match cell {
-                Some(game::Player::P1) => "1",
-                Some(game::Player::P2) => "2",
-                None => " ",
-            }
-        );
+    if is_grid {
+        print!("{margin}");
+        for col in 0..game::BOARD.cols {
+            print!("  {:<2}", game::column_name(col));
+        }
+        println!();
+    }
+    println!("{margin}{}", *LINE);
+    for (row, cells) in state.board.chunks(game::BOARD.cols).enumerate() {
+        if is_grid {
+            print!("{:>width$} ", row + 1, width = margin.len() - 1);
+        }
+        print!("|");
+        for cell in cells {
+            print!(
+                " {} |",
+                match cell {
+                    Some(game::Player::P1) => "1",
+                    Some(game::Player::P2) => "2",
+                    None => " ",
+                }
+            );
+        }
+        println!();
+        println!("{margin}{}", *LINE);
     }
-    println!("");
-    println!("{}", *LINE);
 }
 
 pub fn print_result(result: &game::Result, orig_state: &game::State) {
@@ -0,0 +1,231 @@
+use once_cell::sync::Lazy;
+
+pub static BOARD: Lazy<Board> = Lazy::new(|| match std::env::var("BOARD_GRAPH") {
+    Ok(path) => (|| -> anyhow::Result<Board> {
+        let edge_list = std::fs::read_to_string(&path)?;
+        Board::graph(&edge_list)
+    })()
+    .unwrap_or_else(|err| panic!("failed to load the board from {path}: {err}")),
+    Err(_) => Board::grid(*crate::BOARD_ROWS, *crate::BOARD_SIZE),
+});
+
+/// the topology of the board, i.e. which cells are adjacent to each other
+///
+/// cells are indexed row by row, i.e. `row * cols + col`
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+    Debug,
+)]
+pub struct Board {
+    pub rows: usize,
+    pub cols: usize,
+    pub neighbours: Vec<Vec<usize>>, // indexed by cell
+    pub symmetries: Vec<Symmetry>,   // note: the identity always comes first
+}
+
+/// a permutation of cells preserving the adjacency, e.g. mirroring a single-row board
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+    Debug,
+    // hashable:
+    PartialEq,
+    Eq,
+    Hash,
+)]
+pub struct Symmetry {
+    cells: Vec<usize>, // the image of each cell
+}
+
+impl Symmetry {
+    pub fn identity(cells: usize) -> Symmetry {
+        Symmetry {
+            cells: (0..cells).collect(),
+        }
+    }
+
+    pub fn cell(&self, cell: usize) -> usize {
+        self.cells[cell]
+    }
+
+    pub fn action(&self, action: &crate::Action) -> crate::Action {
+        match *action {
+            crate::Action::Place { row, col } => {
+                crate::BOARD.action(self.cell(crate::BOARD.cell(row, col)))
+            }
+            crate::Action::Pass => crate::Action::Pass,
+        }
+    }
+
+    pub fn inverse(&self) -> Symmetry {
+        let mut cells = vec![0; self.cells.len()];
+        for (cell, &image) in self.cells.iter().enumerate() {
+            cells[image] = cell;
+        }
+        Symmetry { cells }
+    }
+}
+
+impl Board {
+    /// a free-placement grid where a cell is adjacent to all 8 cells around it (horizontally, vertically or diagonally)
+    ///
+    /// note: a grid with a single row is the original Connect Two board
+    pub fn grid(rows: usize, cols: usize) -> Board {
+        let neighbours = (0..rows * cols)
+            .map(|cell| {
+                let (row, col) = ((cell / cols) as isize, (cell % cols) as isize);
+                [
+                    (-1, -1),
+                    (-1, 0),
+                    (-1, 1),
+                    (0, -1),
+                    (0, 1),
+                    (1, -1),
+                    (1, 0),
+                    (1, 1),
+                ]
+                .into_iter()
+                .map(|(d_row, d_col)| (row + d_row, col + d_col))
+                .filter(|&(r, c)| {
+                    (0..rows as isize).contains(&r) && (0..cols as isize).contains(&c)
+                })
+                .map(|(r, c)| r as usize * cols + c as usize)
+                .collect()
+            })
+            .collect();
+
+        // (row, col, rows, cols) -> (row, col) of the image
+        type Transform = fn(usize, usize, usize, usize) -> (usize, usize);
+        let transforms: [Transform; 8] = [
+            // the symmetries of a rectangle:
+            |r, c, _, _| (r, c),
+            |r, c, _, cols| (r, cols - 1 - c),
+            |r, c, rows, _| (rows - 1 - r, c),
+            |r, c, rows, cols| (rows - 1 - r, cols - 1 - c),
+            // the diagonal mirrors and rotations of a square:
+            |r, c, _, _| (c, r),
+            |r, c, rows, cols| (cols - 1 - c, rows - 1 - r),
+            |r, c, rows, _| (c, rows - 1 - r),
+            |r, c, _, cols| (cols - 1 - c, r),
+        ];
+        let mut symmetries: Vec<Symmetry> = Vec::new();
+        for transform in &transforms[..if rows == cols { 8 } else { 4 }] {
+            let symmetry = Symmetry {
+                cells: (0..rows * cols)
+                    .map(|cell| {
+                        let (r, c) = transform(cell / cols, cell % cols, rows, cols);
+                        r * cols + c
+                    })
+                    .collect(),
+            };
+            // e.g. mirroring a single row vertically is the identity
+            if !symmetries.contains(&symmetry) {
+                symmetries.push(symmetry);
+            }
+        }
+
+        Board {
+            rows,
+            cols,
+            neighbours,
+            symmetries,
+        }
+    }
+
+    /// an arbitrary undirected graph where cells are nodes and adjacent cells are connected by an edge
+    ///
+    /// the edge list has one edge per line, e.g. `0 1`, and a line with a single node declares a node without edges;
+    /// nodes are numbered from `0` and `#` starts a comment
+    ///
+    /// note: the graph is treated as a single row of cells without symmetries
+    pub fn graph(edge_list: &str) -> anyhow::Result<Board> {
+        let mut neighbours: Vec<Vec<usize>> = Vec::new();
+        for (i, line) in edge_list.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let nodes = line
+                .split_whitespace()
+                .map(|s| s.parse::<usize>())
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|err| anyhow::anyhow!("line {}: {err}", i + 1))?;
+            anyhow::ensure!(
+                matches!(nodes.len(), 1 | 2),
+                "line {}: expected an edge `<node> <node>` or a single `<node>`",
+                i + 1
+            );
+            let max_node = *nodes.iter().max().unwrap();
+            if neighbours.len() <= max_node {
+                neighbours.resize(max_node + 1, Vec::new());
+            }
+            if let [a, b] = nodes[..] {
+                anyhow::ensure!(
+                    a != b,
+                    "line {}: a node cannot be adjacent to itself",
+                    i + 1
+                );
+                if !neighbours[a].contains(&b) {
+                    neighbours[a].push(b);
+                    neighbours[b].push(a);
+                }
+            }
+        }
+        anyhow::ensure!(!neighbours.is_empty(), "the graph has no nodes");
+        Ok(Board {
+            rows: 1,
+            cols: neighbours.len(),
+            symmetries: vec![Symmetry::identity(neighbours.len())],
+            neighbours,
+        })
+    }
+
+    pub fn cells(&self) -> usize {
+        self.neighbours.len()
+    }
+
+    pub fn cell(&self, row: usize, col: usize) -> usize {
+        row * self.cols + col
+    }
+
+    pub fn action(&self, cell: usize) -> crate::Action {
+        crate::Action::Place {
+            row: cell / self.cols,
+            col: cell % self.cols,
+        }
+    }
+}
+
+/// spreadsheet-like column names, i.e. `a`, `b`, ..., `z`, `aa`, `ab`, ...
+pub fn column_name(col: usize) -> String {
+    let mut name = Vec::new();
+    let mut n = col + 1;
+    while n > 0 {
+        n -= 1;
+        name.push(b'a' + (n % 26) as u8);
+        n /= 26;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap()
+}
+
+/// inverse of `column_name`, where the column should be on `crate::BOARD`
+pub fn parse_column_name(name: &str) -> anyhow::Result<usize> {
+    anyhow::ensure!(
+        !name.is_empty() && name.bytes().all(|b| b.is_ascii_lowercase()),
+        "invalid column name!"
+    );
+    // note: checked, since an arbitrarily long name comes from user input
+    let col = name
+        .bytes()
+        .try_fold(0usize, |n, b| {
+            n.checked_mul(26)?.checked_add((b - b'a') as usize + 1)
+        })
+        .map(|n| n - 1)
+        .filter(|&col| col < crate::BOARD.cols);
+    col.ok_or(anyhow::anyhow!(
+        "no column `{name}` on a board of {} columns!",
+        crate::BOARD.cols
+    ))
+}
@@ -1,17 +1,59 @@
+mod board;
 mod player;
+mod policy;
 mod state;
+pub use board::*;
 pub use player::*;
+pub use policy::*;
 pub use state::*;
 
 use once_cell::sync::Lazy;
 
+/// number of columns
 pub static BOARD_SIZE: Lazy<usize> = Lazy::new(|| {
-    match || -> anyhow::Result<usize> {
+    (|| -> anyhow::Result<usize> {
         let val = std::env::var("BOARD_SIZE")?;
         let val = val.parse::<usize>()?;
         Ok(val)
-    }() {
-        Ok(val) => val,
-        Err(_) => 4,
-    }
+    })()
+    .unwrap_or(4)
+});
+
+/// number of rows, the board is a single row by default
+pub static BOARD_ROWS: Lazy<usize> = Lazy::new(|| {
+    (|| -> anyhow::Result<usize> {
+        let val = std::env::var("BOARD_ROWS")?;
+        let val = val.parse::<usize>()?;
+        Ok(val)
+    })()
+    .unwrap_or(1)
+});
+
+/// number of stones to place in each turn, e.g. `1,2` (Connect6-style) means the first turn places 1 stone,
+/// then every later turn places 2 stones
+///
+/// the last entry repeats forever, so the default `1` is the usual one-stone-per-turn game
+pub static TURN_SCHEDULE: Lazy<Vec<usize>> = Lazy::new(|| {
+    (|| -> anyhow::Result<Vec<usize>> {
+        let val = std::env::var("TURN_SCHEDULE")?;
+        let val = val
+            .split(',')
+            .map(|s| s.trim().parse::<usize>())
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        anyhow::ensure!(
+            !val.is_empty() && val.iter().all(|&n| n > 0),
+            "every turn should place at least 1 stone!"
+        );
+        Ok(val)
+    })()
+    .unwrap_or(vec![1])
+});
+
+/// whether a player can pass instead of occupying a cell, the game is a draw once all players pass consecutively
+pub static ALLOW_PASS: Lazy<bool> = Lazy::new(|| {
+    (|| -> anyhow::Result<bool> {
+        let val = std::env::var("ALLOW_PASS")?;
+        Ok(val == "1")
+    })()
+    .unwrap_or_default()
 });
@@ -9,6 +9,9 @@
     PartialEq,
     Eq,
     Hash,
+    // serializable:
+    serde::Serialize,
+    serde::Deserialize,
 )]
 pub enum Player {
     #[default] // default player to first take action
@@ -16,6 +19,8 @@ pub enum Player {
     P2,
 }
 
+pub const PLAYER_COUNT: usize = 2;
+
 impl Player {
     pub fn new() -> Player {
         Player::default()
@@ -28,3 +33,16 @@ impl Player {
         }
     }
 }
+
+/// `1` or `2`
+impl std::str::FromStr for Player {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Player> {
+        match s {
+            "1" => Ok(Player::P1),
+            "2" => Ok(Player::P2),
+            _ => anyhow::bail!("unknown player `{s}`, expected `1` or `2`"),
+        }
+    }
+}
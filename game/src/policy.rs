@@ -0,0 +1,76 @@
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// something picking actions, e.g. a human or a bot, so that any two can play against each other
+pub trait Policy {
+    /// an action for the player to act at `state`
+    ///
+    /// ASSUMPTION: the game is not over at `state`, i.e. there is any legal action
+    fn choose(&mut self, state: &crate::State) -> crate::Action;
+}
+
+/// uniformly random actions
+pub struct RandomPolicy {
+    rng: rand::rngs::StdRng,
+}
+
+impl RandomPolicy {
+    pub fn new(seed: u64) -> RandomPolicy {
+        RandomPolicy {
+            rng: rand::rngs::StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Policy for RandomPolicy {
+    fn choose(&mut self, state: &crate::State) -> crate::Action {
+        *state.all_actions().choose(&mut self.rng).unwrap()
+    }
+}
+
+/// wins immediately if possible, otherwise blocks a cell where the opponent would win immediately,
+/// otherwise places a random stone (and only passes if nothing else is legal)
+pub struct GreedyPolicy {
+    rng: rand::rngs::StdRng,
+}
+
+impl GreedyPolicy {
+    pub fn new(seed: u64) -> GreedyPolicy {
+        GreedyPolicy {
+            rng: rand::rngs::StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Policy for GreedyPolicy {
+    fn choose(&mut self, state: &crate::State) -> crate::Action {
+        let placements = state
+            .all_actions()
+            .into_iter()
+            .filter_map(|act| match act {
+                crate::Action::Place { row, col } => Some((act, crate::BOARD.cell(row, col))),
+                crate::Action::Pass => None,
+            })
+            .collect::<Vec<_>>();
+        let next_to = |player: crate::Player| {
+            placements
+                .iter()
+                .filter(|(_, cell)| {
+                    crate::BOARD.neighbours[*cell]
+                        .iter()
+                        .any(|&i| state.board[i] == Some(player))
+                })
+                .map(|(act, _)| *act)
+                .collect::<Vec<_>>()
+        };
+
+        let wins = next_to(state.player_to_act);
+        let blocks = next_to(state.player_to_act.next());
+        let all = placements.iter().map(|(act, _)| *act).collect::<Vec<_>>();
+        [wins, blocks, all]
+            .into_iter()
+            .find(|acts| !acts.is_empty())
+            .and_then(|acts| acts.choose(&mut self.rng).copied())
+            .unwrap_or(crate::Action::Pass)
+    }
+}
@@ -1,10 +1,10 @@
+use anyhow::Context;
 use once_cell::sync::Lazy;
 
 #[derive(
     // sane defaults for value objects:
     Clone,
     Copy,
-    derive_more::Display,
     Debug,
     // hashable:
     PartialEq,
@@ -13,20 +13,68 @@ use once_cell::sync::Lazy;
     // sortable:
     PartialOrd,
     Ord,
+    // serializable:
+    serde::Serialize,
+    serde::Deserialize,
 )]
-#[display(fmt = "({})", col)]
-pub struct Action {
-    pub col: usize,
+pub enum Action {
+    Place { row: usize, col: usize },
+    Pass, // only allowed with `crate::ALLOW_PASS`
 }
 
 impl Action {
-    pub fn new(state: &State, col: usize) -> anyhow::Result<Action> {
-        let new_action = Action { col };
+    pub fn new(state: &State, row: usize, col: usize) -> anyhow::Result<Action> {
+        Action::Place { row, col }.validate(state)
+    }
+
+    pub fn validate(self, state: &State) -> anyhow::Result<Action> {
         anyhow::ensure!(
-            state.all_actions().contains(&new_action),
+            state.all_actions().contains(&self),
             "invalid action for the current state!"
         );
-        Ok(new_action)
+        Ok(self)
+    }
+}
+
+/// `(col)` on a single-row board, spreadsheet-like coordinates (e.g. `b3`) on a grid
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::Place { col, .. } if crate::BOARD.rows == 1 => write!(f, "({col})"),
+            Action::Place { row, col } => write!(f, "{}{}", crate::column_name(*col), row + 1),
+            Action::Pass => write!(f, "pass"),
+        }
+    }
+}
+
+/// inverse of `Display`, except that the brackets are optional on a single-row board
+///
+/// note: the parsed action is not validated against any state, see `Action::validate`
+impl std::str::FromStr for Action {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Action> {
+        if s == "pass" {
+            Ok(Action::Pass)
+        } else if crate::BOARD.rows == 1 {
+            let col = s
+                .trim_start_matches('(')
+                .trim_end_matches(')')
+                .parse::<usize>()
+                .map_err(|_| anyhow::anyhow!("you should enter a nonnegative integer!"))?;
+            Ok(Action::Place { row: 0, col })
+        } else {
+            (|| -> anyhow::Result<Action> {
+                let split_at = s
+                    .find(|c: char| c.is_ascii_digit())
+                    .ok_or(anyhow::anyhow!("missing row"))?;
+                let col = crate::parse_column_name(&s[..split_at])?;
+                let row = s[split_at..].parse::<usize>()?;
+                anyhow::ensure!(row > 0, "rows start from 1");
+                Ok(Action::Place { row: row - 1, col })
+            })()
+            .map_err(|_| anyhow::anyhow!("you should enter a cell like `b3`!"))
+        }
     }
 }
 
@@ -36,6 +84,9 @@ impl Action {
     Copy,
     derive_more::Display,
     Debug,
+    // serializable:
+    serde::Serialize,
+    serde::Deserialize,
 )]
 pub enum Result {
     WaitingNextAction,
@@ -51,61 +102,191 @@ pub type Cell = Option<crate::Player>;
     PartialEq,
     Eq,
     Hash,
+    // serializable:
+    serde::Serialize,
+    serde::Deserialize,
 )]
 pub struct State {
     pub board: Vec<Cell>,
     pub player_to_act: crate::Player,
+
+    // progress of the current turn, see `crate::TURN_SCHEDULE`:
+    pub stones_left: usize,
+    pub turn: usize, // index into `crate::TURN_SCHEDULE`, stays at the last entry once reached
+
+    pub passes: usize, // number of consecutive passes, see `crate::ALLOW_PASS`
 }
 
 // OPTIMIZATION: can be referenced by outside
-pub static INITIAL_STATE: Lazy<State> = Lazy::new(|| State {
-    // (ref.) [How to initialize Vec<Option<T>> with None](https://users.rust-lang.org/t/how-to-initialize-vec-option-t-with-none/30580)
-    board: std::iter::repeat(None).take(*crate::BOARD_SIZE).collect(),
-    player_to_act: crate::Player::new(),
-});
+/// an empty board with P1 to act, unless configured via env `START_POSITION` and `START_PLAYER`, see `State::initial`
+pub static INITIAL_STATE: Lazy<State> =
+    Lazy::new(|| State::initial().unwrap_or_else(|err| panic!("{err}")));
+
+impl Default for State {
+    fn default() -> Self {
+        INITIAL_STATE.clone()
+    }
+}
 
 impl State {
     pub fn new() -> State {
-        INITIAL_STATE.clone()
+        State::default()
+    }
+
+    /// the state configured via env `START_POSITION` and `START_PLAYER`, see `INITIAL_STATE`
+    ///
+    /// note: a binary should call this up front, so that an invalid setting fails with an error naming it rather than
+    /// panicking wherever `INITIAL_STATE` is first used
+    pub fn initial() -> anyhow::Result<State> {
+        let player = match std::env::var("START_PLAYER") {
+            Ok(val) => val
+                .parse::<crate::Player>()
+                .with_context(|| format!("invalid START_PLAYER `{val}`"))?,
+            Err(_) => crate::Player::new(),
+        };
+        match std::env::var("START_POSITION") {
+            Ok(val) => State::from_position(&val, player)
+                .with_context(|| format!("invalid START_POSITION `{val}`")),
+            Err(_) => Ok(State {
+                // (ref.) [How to initialize Vec<Option<T>> with None](https://users.rust-lang.org/t/how-to-initialize-vec-option-t-with-none/30580)
+                board: std::iter::repeat_n(None, crate::BOARD.cells()).collect(),
+                player_to_act: player,
+                stones_left: crate::TURN_SCHEDULE[0],
+                turn: 0,
+                passes: 0,
+            }),
+        }
+    }
+
+    /// a legal position to start a game from, e.g. `1,_,2,_` (or `1_2_`), where rows of a grid are separated by `/`
+    pub fn from_position(position: &str, player_to_act: crate::Player) -> anyhow::Result<State> {
+        let board = position
+            .chars()
+            .filter(|c| !matches!(c, ',' | '/' | ' '))
+            .map(|c| match c {
+                '_' => Ok(None),
+                '1' => Ok(Some(crate::Player::P1)),
+                '2' => Ok(Some(crate::Player::P2)),
+                _ => Err(anyhow::anyhow!(
+                    "unknown cell `{c}`, expected one of `_`, `1`, `2`"
+                )),
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        anyhow::ensure!(
+            board.len() == crate::BOARD.cells(),
+            "expected {} cells but got {}",
+            crate::BOARD.cells(),
+            board.len()
+        );
+        anyhow::ensure!(
+            board.iter().any(|cell| cell.is_none()),
+            "the board is already full"
+        );
+        anyhow::ensure!(
+            board.iter().enumerate().all(|(i, cell)| {
+                cell.is_none()
+                    || crate::BOARD.neighbours[i]
+                        .iter()
+                        .all(|&j| board[j] != *cell)
+            }),
+            "the game is already won"
+        );
+        let stones = |player: crate::Player| board.iter().filter(|&&c| c == Some(player)).count();
+        let (own, other) = (stones(player_to_act), stones(player_to_act.next()));
+        let (turn, stones_left) = if *crate::ALLOW_PASS {
+            // note: any counts can be reached by passing, so the position starts a fresh turn, which is only well
+            // defined when every turn places the same number of stones
+            anyhow::ensure!(
+                crate::TURN_SCHEDULE
+                    .iter()
+                    .all(|&n| n == crate::TURN_SCHEDULE[0]),
+                "the turn is ambiguous when passing with a turn schedule of {:?}",
+                *crate::TURN_SCHEDULE
+            );
+            (crate::TURN_SCHEDULE.len() - 1, crate::TURN_SCHEDULE[0])
+        } else {
+            State::replay_turns(own, other).ok_or(anyhow::anyhow!(
+                "{player_to_act} cannot be to act with {own} stones against {other}"
+            ))?
+        };
+        Ok(State {
+            board,
+            player_to_act,
+            stones_left,
+            turn,
+            passes: 0,
+        })
+    }
+
+    /// the `(turn, stones_left)` at which the player to act has placed `own` stones and the opponent `other` stones,
+    /// if any, see `crate::TURN_SCHEDULE`
+    ///
+    /// note: the total number of stones placed grows with every action, so at most one turn matches
+    fn replay_turns(own: usize, other: usize) -> Option<(usize, usize)> {
+        let stones_per_turn =
+            |turn: usize| crate::TURN_SCHEDULE[turn.min(crate::TURN_SCHEDULE.len() - 1)];
+        // the stones placed before `turn` by the player to act at `turn` and by the opponent
+        let (mut own_before, mut other_before) = (0, 0);
+        for turn in 0.. {
+            if own_before + other_before > own + other {
+                return None;
+            }
+            let stones = stones_per_turn(turn);
+            if other == other_before && own >= own_before && own - own_before < stones {
+                return Some((
+                    turn.min(crate::TURN_SCHEDULE.len() - 1),
+                    stones - (own - own_before),
+                ));
+            }
+            // the turn passes to the opponent
+            (own_before, other_before) = (other_before, own_before + stones);
+        }
+        unreachable!()
     }
 
     pub fn all_actions(&self) -> Vec<crate::Action> {
         self.board
             .iter()
             .enumerate()
-            .filter_map(|(col, cell)| match cell {
-                None => Some(crate::Action { col }),
+            .filter_map(|(i, cell)| match cell {
+                None => Some(crate::BOARD.action(i)),
                 Some(_) => None,
             })
+            .chain((*crate::ALLOW_PASS).then_some(crate::Action::Pass))
             .collect()
     }
 
     pub fn step(&self, action: &crate::Action) -> (State, crate::Result) {
-        let new_state = State {
-            board: self
-                .board
-                .iter()
-                .enumerate()
-                .map(|(col, cell)| {
-                    if col == action.col {
-                        Some(self.player_to_act)
-                    } else {
-                        *cell
-                    }
-                })
-                .collect(),
-            player_to_act: self.player_to_act.next(),
+        let mut new_state = self.clone();
+        let is_win = match *action {
+            crate::Action::Place { row, col } => {
+                let action_cell = crate::BOARD.cell(row, col);
+                new_state.board[action_cell] = Some(self.player_to_act);
+                new_state.stones_left -= 1;
+                new_state.passes = 0;
+                crate::BOARD.neighbours[action_cell]
+                    .iter()
+                    .any(|&i| self.board[i].is_some_and(|p| p == self.player_to_act))
+            }
+            crate::Action::Pass => {
+                // passing gives up the rest of the current turn
+                new_state.stones_left = 0;
+                new_state.passes += 1;
+                false
+            }
         };
+        // the player keeps acting until all stones of the current turn are placed
+        if new_state.stones_left == 0 {
+            new_state.turn = (self.turn + 1).min(crate::TURN_SCHEDULE.len() - 1);
+            new_state.player_to_act = self.player_to_act.next();
+            new_state.stones_left = crate::TURN_SCHEDULE[new_state.turn];
+        }
 
-        let result = if action.col > 0
-            && self.board[action.col - 1].is_some_and(|p| p == self.player_to_act)
-        {
+        let result = if is_win {
             crate::Result::Win
-        } else if action.col < *crate::BOARD_SIZE - 1
-            && self.board[action.col + 1].is_some_and(|p| p == self.player_to_act)
+        } else if new_state.board.iter().all(|cell| cell.is_some())
+            || new_state.passes == crate::PLAYER_COUNT
         {
-            crate::Result::Win
-        } else if new_state.all_actions().is_empty() {
             crate::Result::Draw
         } else {
             crate::Result::WaitingNextAction
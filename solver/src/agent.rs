@@ -1,7 +1,13 @@
-use std::collections::{HashMap, HashSet};
+use rayon::prelude::*;
+use std::collections::HashMap;
 
-pub type StateAction = (crate::EncodedState, game::Action);
+pub(crate) type StateAction = (crate::EncodedState, game::Action);
 
+#[derive(
+    // serializable:
+    serde::Serialize,
+    serde::Deserialize,
+)]
 pub struct Agent(HashMap<crate::EncodedState, HashMap<game::Action, crate::Reward>>);
 
 impl Agent {
@@ -9,248 +15,492 @@ impl Agent {
         Agent(HashMap::default())
     }
 
-    pub fn reward(
+    // note: the tables are keyed by canonical states, see `crate::EncodedState::canonical`,
+    // so the actions are transformed from and back to the orientation of the given state
+
+    pub fn contains(&self, at_encoded_state: &crate::EncodedState) -> bool {
+        self.0.contains_key(&at_encoded_state.canonical().0)
+    }
+
+    pub(crate) fn reward(
         &self,
         at_encoded_state: &crate::EncodedState,
         action: &game::Action,
     ) -> &crate::Reward {
-        &self.0[at_encoded_state][action]
+        let (canonical, symmetry) = at_encoded_state.canonical();
+        &self.0[&canonical][&symmetry.action(action)]
+    }
+
+    fn reward_mut(
+        &mut self,
+        at_encoded_state: &crate::EncodedState,
+        action: &game::Action,
+    ) -> &mut crate::Reward {
+        let (canonical, symmetry) = at_encoded_state.canonical();
+        self.0
+            .get_mut(&canonical)
+            .and_then(|m| m.get_mut(&symmetry.action(action)))
+            .unwrap()
     }
 
     pub fn max_value(&self, at_encoded_state: &crate::EncodedState) -> crate::Value {
-        self.0[at_encoded_state]
+        self.0[&at_encoded_state.canonical().0]
             .values()
             .max_by(|a, b| a.value.cmp(&b.value))
-            .and_then(|r| Some(r.value))
+            .map(|r| r.value)
             .unwrap_or_default()
     }
 
     pub fn optimal_actions(&self, at_encoded_state: &crate::EncodedState) -> Vec<game::Action> {
-        self.0[at_encoded_state]
+        let (canonical, symmetry) = at_encoded_state.canonical();
+        let max_value = self.max_value(&canonical);
+        let inverse = symmetry.inverse();
+        self.0[&canonical]
             .iter()
-            // (ref.) [How do I idiomatically convert a bool to an Option or Result in Rust?](https://stackoverflow.com/questions/54841351/how-do-i-idiomatically-convert-a-bool-to-an-option-or-result-in-rust)
-            .filter_map(|(action, reward)| {
-                (reward.value == self.max_value(at_encoded_state)).then_some(*action)
-            })
+            .filter(|(_, reward)| reward.value == max_value)
+            .map(|(action, _)| inverse.action(action))
+            .collect()
+    }
+
+    /// every action at the state, see `Agent::optimal_actions`
+    pub fn actions(&self, at_encoded_state: &crate::EncodedState) -> Vec<game::Action> {
+        let (canonical, symmetry) = at_encoded_state.canonical();
+        let inverse = symmetry.inverse();
+        self.0[&canonical]
+            .keys()
+            .map(|action| inverse.action(action))
             .collect()
     }
 
-    fn init_agents(p1_agent: &mut Agent, p2_agent: &mut Agent) {
-        type Unexplored = HashSet<(
-            game::State,
-            crate::EncodedState, // OPTIMIZATION: reuse encoded_state during exploring
-        )>;
+    /// every (canonical) state in the table, sorted
+    pub fn encoded_states(&self) -> Vec<&crate::EncodedState> {
+        let mut v = self.0.keys().collect::<Vec<_>>();
+        v.sort_unstable();
+        v
+    }
+
+    /// the number of states where any action is visited in `epoch`
+    pub fn states_visited_at(&self, epoch: u32) -> usize {
+        self.0
+            .values()
+            .filter(|rewards| rewards.values().any(|r| r.last_visited_at == epoch))
+            .count()
+    }
+
+    /// the number of actions whose value is updated in `epoch`
+    pub fn values_updated_at(&self, epoch: u32) -> usize {
+        self.0
+            .values()
+            .flat_map(|rewards| rewards.values())
+            .filter(|r| r.last_updated_at == epoch)
+            .count()
+    }
+
+    /// the number of states where no action has a defined value yet
+    pub fn undefined_states(&self) -> usize {
+        self.0
+            .values()
+            .filter(|rewards| {
+                rewards
+                    .values()
+                    .all(|r| matches!(r.value, crate::Value::UNDEFINED))
+            })
+            .count()
+    }
+
+    /// `new_init_reward` creates a function for each thread, which can refine a newly created reward of taking
+    /// the action at the state
+    fn init_agents<F>(
+        p1_agent: &mut Agent,
+        p2_agent: &mut Agent,
+        initial_state: &game::State,
+        new_init_reward: impl Fn() -> F,
+    ) where
+        F: FnMut(&game::State, &game::Action, &mut crate::Reward) + Send,
+    {
+        // OPTIMIZATION: only one of the symmetric states is explored
+        type Unexplored = HashMap<
+            crate::EncodedState, // the canonical encoded state
+            (game::State, &'static game::Symmetry),
+        >;
+        let canonical = |s: game::State| {
+            let (encoded_s, symmetry) = crate::EncodedState::new(&s).canonical();
+            (encoded_s, (s, symmetry))
+        };
+
+        // OPTIMIZATION: each thread keeps its own function, e.g. to reuse a search across the whole BFS
+        // note: only the thread running it locks each function
+        let init_rewards = (0..rayon::current_num_threads())
+            .map(|_| std::sync::Mutex::new(new_init_reward()))
+            .collect::<Vec<_>>();
 
-        // BFS
+        // BFS, where each level is expanded in parallel
         // ASSUMPTION: there are no loops in states, i.e. the state-space is a one-way tree without loops
-        let mut curr_player = game::Player::new();
-        let mut unexplored: Unexplored = HashSet::from([(
-            game::State::new().clone(),
-            crate::EncodedState::new(&game::INITIAL_STATE),
-        )]);
+        let mut unexplored: Unexplored = HashMap::from([canonical(initial_state.clone())]);
+        let mut depth = 1;
         while !unexplored.is_empty() {
-            let curr_agent = match curr_player {
-                game::Player::P1 => &mut *p1_agent,
-                game::Player::P2 => &mut *p2_agent,
-            };
-            let mut new_unexplored: Unexplored = HashSet::new();
-            for (s, encoded_s) in unexplored {
-                for act in s.all_actions() {
-                    let (s_next, result) = s.step(&act);
-                    let encoded_s_next = crate::EncodedState::new(&s_next);
+            let expanded = unexplored
+                .into_par_iter()
+                .map(|(encoded_s, (s, symmetry))| {
+                    let mut init_reward = init_rewards[rayon::current_thread_index().unwrap_or(0)]
+                        .lock()
+                        .unwrap();
+                    let rewards = s
+                        .all_actions()
+                        .into_iter()
+                        .map(|act| {
+                            let (s_next, result) = s.step(&act);
+                            let (encoded_s_next, s_next) = canonical(s_next);
+                            let mut reward = crate::Reward::new(&encoded_s_next, &result);
+                            init_reward(&s, &act, &mut reward);
+                            let s_next = matches!(result, game::Result::WaitingNextAction)
+                                .then_some((encoded_s_next, s_next));
+                            (symmetry.action(&act), reward, s_next)
+                        })
+                        .collect::<Vec<_>>();
+                    (encoded_s, s.player_to_act, rewards)
+                })
+                .collect::<Vec<_>>();
 
+            let mut new_unexplored: Unexplored = HashMap::new();
+            for (encoded_s, player_to_act, rewards) in expanded {
+                // note: the same player can act in consecutive states, see `game::TURN_SCHEDULE`
+                let curr_agent = match player_to_act {
+                    game::Player::P1 => &mut *p1_agent,
+                    game::Player::P2 => &mut *p2_agent,
+                };
+                for (act, reward, s_next) in rewards {
                     // init agent
                     // (ref.) [How to lookup from and insert into a HashMap efficiently?](https://stackoverflow.com/questions/28512394/how-to-lookup-from-and-insert-into-a-hashmap-efficiently)
                     curr_agent
                         .0
-                        .entry(encoded_s.clone())
+                        .entry(encoded_s)
                         .or_insert(HashMap::new())
                         .entry(act)
-                        .or_insert(crate::Reward::new(&encoded_s_next, &result));
+                        .or_insert(reward);
 
-                    if matches!(result, game::Result::WaitingNextAction) {
-                        new_unexplored.insert((s_next, encoded_s_next));
+                    if let Some((encoded_s_next, s_next)) = s_next {
+                        new_unexplored.entry(encoded_s_next).or_insert(s_next);
                     }
                 }
             }
+            log::debug!(
+                target: "solver::init",
+                depth = depth,
+                states = new_unexplored.len();
+                "expanded"
+            );
             unexplored = new_unexplored;
-            curr_player = curr_player.next();
+            depth += 1;
         }
     }
 
-    /// return `true` if any value is updated, `false` otherwise
+    /// `value` is the value of the last state-action in the trajectory, where each state-action comes with its ply
     fn backtrack(
-        agent: &mut Agent,
-        trajectory: &Vec<crate::StateAction>,
+        epoch_values: &mut EpochValues,
+        trajectory: &[(crate::StateAction, u32)],
         value: crate::Value,
-        curr_epoch: &u32,
-    ) -> bool {
+    ) {
         let mut has_update = false;
-        for state_action in trajectory.iter().rev() {
-            let curr_reward = agent
-                .0
-                .get_mut(&state_action.0)
-                .and_then(|m| m.get_mut(&state_action.1))
-                .unwrap();
-
-            if curr_reward.last_visited_at == *curr_epoch {
-                if value > curr_reward.value {
-                    curr_reward.value = value;
-                    has_update = true;
-                }
-            } else {
-                if value != curr_reward.value {
-                    curr_reward.value = value;
-                    has_update = true;
-                }
+        let last_ply = trajectory.last().map(|(_, ply)| *ply).unwrap_or_default();
+        for (state_action, ply) in trajectory.iter().rev() {
+            if epoch_values.visit(state_action, value.later(last_ply - ply)) {
+                has_update = true;
             }
-
-            curr_reward.last_visited_at = *curr_epoch;
         }
-        // debug
-        println!(
-            "backtracking...  [{value}] ({})  (has_update={has_update})",
+        log::trace!(
+            target: "solver::backtrack",
+            value:% = value,
+            has_update = has_update;
+            "{}",
             trajectory
                 .iter()
-                .map(|(state, action)| format!("{} -> {}", state, action))
+                .map(|((state, action), _)| format!("{} -> {}", state, action))
                 .collect::<Vec<_>>()
                 .join(" ")
         );
-        has_update
     }
 
     /// return `true` if any value is updated, `false` otherwise
+    ///
+    /// note: the optimal actions at the initial encoded states are explored in parallel, each on top of the values of
+    /// the previous epoch, then merged in the order of the actions, so that the values do not depend on the number of
+    /// threads
     fn train(
         target: &mut Agent,
         opponent: &Agent,
         curr_epoch: &u32,
-        initial_encoded_states: &Vec<&crate::EncodedState>,
+        initial_encoded_states: &[&crate::EncodedState],
     ) -> bool {
-        // debug
-        {
-            println!();
-            println!("curr_epoch = {curr_epoch}:");
-        }
+        let initial_state_actions = initial_encoded_states
+            .iter()
+            .flat_map(|s| {
+                target
+                    .optimal_actions(s)
+                    .into_iter()
+                    .map(|act| (**s, act))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let visited = initial_state_actions
+            .into_par_iter()
+            .map(|state_action| {
+                let mut epoch_values = EpochValues::new(target);
+                Agent::train_from(&mut epoch_values, opponent, state_action);
+                epoch_values.visited
+            })
+            .collect::<Vec<_>>();
 
+        // as if explored one after the other, i.e. a later visit in the same epoch only raises the value
         let mut has_update = false;
+        for ((s, act), (value, mut is_updated)) in visited.into_iter().flatten() {
+            let reward = target.reward_mut(&s, &act);
+            if reward.last_visited_at == *curr_epoch {
+                is_updated = value > reward.value;
+                if is_updated {
+                    reward.value = value;
+                }
+            } else {
+                reward.value = value;
+                reward.last_visited_at = *curr_epoch;
+            }
+            if is_updated {
+                reward.last_updated_at = *curr_epoch;
+                has_update = true;
+            }
+        }
+        has_update
+    }
 
+    /// explore the optimal lines after `initial_state_action`, see `Agent::train`
+    fn train_from(
+        epoch_values: &mut EpochValues,
+        opponent: &Agent,
+        initial_state_action: crate::StateAction,
+    ) {
         // DFS without recursion
         // ASSUMPTION: there are no loops in states, i.e. the state-space is a one-way tree without loops
-        let mut trajectory: Vec<crate::StateAction> = Vec::new(); // LIFO
+        let mut trajectory: Vec<(crate::StateAction, u32)> = Vec::new(); // LIFO
         let mut unexplored: Vec<(
             crate::StateAction,
             usize, // trajectory_len: a helper state storing the previous trajectory length
-        )> = initial_encoded_states
-            .iter()
-            .flat_map(|s| {
-                target
-                    .optimal_actions(s)
-                    .iter()
-                    .map(|act| (((*s).clone(), *act), 0usize))
-                    .collect::<Vec<_>>()
-            })
-            .collect();
-        while let Some(((s, act), trajectory_len)) = unexplored.pop() {
+            u32,   // ply: the number of plies from the initial encoded state
+        )> = vec![(initial_state_action, 0, 0)];
+        while let Some(((s, act), trajectory_len, ply)) = unexplored.pop() {
             // (ref.) [How do I get n elements from Vec?](https://www.reddit.com/r/rust/comments/2ooe03/how_do_i_get_n_elements_from_vec/)
             trajectory = {
                 let mut v = trajectory[0..trajectory_len].to_vec();
-                v.push((s.clone(), act));
+                v.push(((s, act), ply));
                 v
             };
 
-            let reward = target.reward(&s, &act);
+            let reward = epoch_values.agent.reward(&s, &act);
             if let Some(value) = {
-                // OPTIMIZATION: use the reward's value as is if the choice is already explored in this epoch
-                if reward.last_visited_at == *curr_epoch {
-                    Some(reward.value)
+                // OPTIMIZATION: use the value as is if the choice is already explored in this epoch
+                if let Some(value) = epoch_values.visited_value(&(s, act)) {
+                    Some(value)
                 } else {
                     match reward.result {
-                        game::Result::Win => Some(crate::Value::WIN),
+                        game::Result::Win => Some(crate::Value::WIN(1)),
                         game::Result::Draw => Some(crate::Value::DRAW),
                         game::Result::WaitingNextAction => {
-                            let mut max_value = crate::Value::new();
+                            let target_player = s.player_to_act();
                             let s_next = &reward.to_encoded_state;
-                            for reward_oppo in opponent
-                                .optimal_actions(&s_next)
-                                .into_iter()
-                                .map(|act_oppo| opponent.reward(&s_next, &act_oppo))
-                            {
-                                match reward_oppo.result {
-                                    game::Result::Win => {
-                                        max_value = max_value.max(crate::Value::LOSE)
-                                    }
-                                    game::Result::Draw => {
-                                        max_value = max_value.max(crate::Value::DRAW)
-                                    }
-                                    game::Result::WaitingNextAction => {
-                                        let s_next_next = &reward_oppo.to_encoded_state;
-                                        unexplored.extend(
-                                            target.optimal_actions(s_next_next).into_iter().map(
-                                                |act| {
-                                                    ((s_next_next.clone(), act), trajectory.len())
-                                                },
-                                            ),
-                                        )
+                            if s_next.player_to_act() == target_player {
+                                // the turn is not finished yet, continue the same trajectory
+                                unexplored.extend(
+                                    epoch_values
+                                        .optimal_actions(s_next)
+                                        .into_iter()
+                                        .map(|act| ((*s_next, act), trajectory.len(), ply + 1)),
+                                );
+                                None
+                            } else {
+                                let mut max_value = crate::Value::new();
+                                // the opponent may also place several stones before the target acts again
+                                let mut unexplored_oppo = vec![(s_next, 1)]; // (state, plies after `s`)
+                                while let Some((s_oppo, plies)) = unexplored_oppo.pop() {
+                                    for reward_oppo in opponent
+                                        .optimal_actions(s_oppo)
+                                        .into_iter()
+                                        .map(|act_oppo| opponent.reward(s_oppo, &act_oppo))
+                                    {
+                                        match reward_oppo.result {
+                                            game::Result::Win => {
+                                                max_value =
+                                                    max_value.max(crate::Value::LOSE(plies + 1))
+                                            }
+                                            game::Result::Draw => {
+                                                max_value = max_value.max(crate::Value::DRAW)
+                                            }
+                                            game::Result::WaitingNextAction => {
+                                                let s_next_next = &reward_oppo.to_encoded_state;
+                                                if s_next_next.player_to_act() == target_player {
+                                                    unexplored.extend(
+                                                        epoch_values
+                                                            .optimal_actions(s_next_next)
+                                                            .into_iter()
+                                                            .map(|act| {
+                                                                (
+                                                                    (*s_next_next, act),
+                                                                    trajectory.len(),
+                                                                    ply + plies + 1,
+                                                                )
+                                                            }),
+                                                    )
+                                                } else {
+                                                    unexplored_oppo.push((s_next_next, plies + 1));
+                                                }
+                                            }
+                                        }
                                     }
                                 }
+                                (!matches!(max_value, crate::Value::UNDEFINED)).then_some(max_value)
                             }
-                            (!matches!(max_value, crate::Value::UNDEFINED)).then_some(max_value)
                         }
                     }
                 }
             } {
-                if Agent::backtrack(target, &trajectory, value, &curr_epoch) {
-                    has_update = true;
+                Agent::backtrack(epoch_values, &trajectory, value);
+            }
+        }
+    }
+
+    /// the encoded states where `player` acts for the first time, assuming `self` is the opponent playing optimally
+    fn first_encoded_states<'a>(
+        &'a self,
+        player: game::Player,
+        encoded_initial_state: &'a crate::EncodedState,
+    ) -> Vec<&'a crate::EncodedState> {
+        let mut encoded_states = Vec::new();
+        let mut unexplored = vec![encoded_initial_state];
+        while let Some(s) = unexplored.pop() {
+            if s.player_to_act() == player {
+                encoded_states.push(s);
+                continue;
+            }
+            for act in self.optimal_actions(s) {
+                let reward = self.reward(s, &act);
+                if matches!(reward.result, game::Result::WaitingNextAction) {
+                    unexplored.push(&reward.to_encoded_state);
                 }
             }
         }
-        has_update
+        encoded_states.sort_unstable();
+        encoded_states.dedup();
+        encoded_states
     }
 
-    pub fn new_trained() -> (Agent, Agent) {
-        let encoded_initial_state: &crate::EncodedState =
-            &crate::EncodedState::new(&game::INITIAL_STATE);
+    /// train both agents for all states reachable from `initial_state`, e.g. `game::INITIAL_STATE`
+    ///
+    /// fails if the training does not converge within `max_epochs` (if any)
+    ///
+    /// note: the epochs take turns, since every epoch follows the values updated by the previous one, but each epoch
+    /// is parallel, see `Agent::train`
+    pub fn new_trained(
+        initial_state: &game::State,
+        max_epochs: Option<u32>,
+    ) -> anyhow::Result<(Agent, Agent, crate::TrainingReport)> {
+        let encoded_initial_state: &crate::EncodedState = &crate::EncodedState::new(initial_state);
 
         let mut p1_agent = Agent::new();
         let mut p2_agent = Agent::new();
 
-        Agent::init_agents(&mut p1_agent, &mut p2_agent);
+        Agent::init_agents(&mut p1_agent, &mut p2_agent, initial_state, || |_, _, _| ());
 
+        let mut report = crate::TrainingReport::new();
         let mut curr_epoch = 0;
-        let mut curr_player = game::Player::new();
+        let mut curr_player = initial_state.player_to_act;
         loop {
             curr_epoch += 1;
-            if !{
-                match curr_player {
-                    game::Player::P1 => Agent::train(
-                        &mut p1_agent,
-                        &p2_agent,
-                        &curr_epoch,
-                        &vec![encoded_initial_state],
-                    ),
-                    game::Player::P2 => Agent::train(
-                        &mut p2_agent,
-                        &p1_agent,
-                        &curr_epoch,
-                        &p1_agent
-                            .optimal_actions(encoded_initial_state)
-                            .iter()
-                            .map(|act| {
-                                &p1_agent.reward(encoded_initial_state, act).to_encoded_state
-                            })
-                            .collect(),
-                    ),
-                }
-            } {
+            let started_at = std::time::Instant::now();
+            let (target, opponent) = match curr_player {
+                game::Player::P1 => (&mut p1_agent, &p2_agent),
+                game::Player::P2 => (&mut p2_agent, &p1_agent),
+            };
+            let has_update = Agent::train(
+                target,
+                opponent,
+                &curr_epoch,
+                &opponent.first_encoded_states(curr_player, encoded_initial_state),
+            );
+            let stats = crate::EpochStats::new(
+                curr_epoch,
+                curr_player,
+                target,
+                opponent,
+                started_at.elapsed(),
+            );
+            log::info!(
+                target: "solver::train",
+                epoch = stats.epoch,
+                player:% = stats.player,
+                states_visited = stats.states_visited,
+                values_changed = stats.values_changed,
+                undefined_states = stats.undefined_states,
+                elapsed:? = stats.elapsed;
+                "trained"
+            );
+            report.epochs.push(stats);
+            if !has_update {
                 break;
             }
-            // debug
-            match curr_player {
-                game::Player::P1 => println!("{p1_agent}"),
-                game::Player::P2 => println!("{p2_agent}"),
+            if max_epochs.is_some_and(|max_epochs| curr_epoch >= max_epochs) {
+                anyhow::bail!(
+                    "the training did not converge within {curr_epoch} epochs, \
+                    where {} values still changed in the last epoch",
+                    report.epochs.last().unwrap().values_changed
+                );
             }
+            log::debug!(
+                target: "solver::train",
+                epoch = curr_epoch,
+                player:% = curr_player;
+                "updated\n{}",
+                match curr_player {
+                    game::Player::P1 => &p1_agent,
+                    game::Player::P2 => &p2_agent,
+                }
+            );
             curr_player = curr_player.next();
         }
+        log::info!(
+            target: "solver::train",
+            "converged after {curr_epoch} epochs in {:.1?}\n{report}",
+            report.elapsed()
+        );
+
+        Ok((p1_agent, p2_agent, report))
+    }
+
+    /// look up the exact value of every action in all states reachable from `initial_state`, see `crate::Retrograde`
+    pub fn new_retrograde(initial_state: &game::State) -> (Agent, Agent) {
+        let mut p1_agent = Agent::new();
+        let mut p2_agent = Agent::new();
+
+        let retrograde = crate::Retrograde::new(initial_state);
+        Agent::init_agents(&mut p1_agent, &mut p2_agent, initial_state, || {
+            |s, act, reward| {
+                reward.value = retrograde.action_value(s, act).unwrap();
+            }
+        });
+
+        (p1_agent, p2_agent)
+    }
+
+    /// search the exact value of every action in all states reachable from `initial_state`, see `crate::Negamax`
+    pub fn new_searched(initial_state: &game::State) -> (Agent, Agent) {
+        let mut p1_agent = Agent::new();
+        let mut p2_agent = Agent::new();
+
+        // OPTIMIZATION: the searches are split across threads, each with its own transposition table
+        // note: the values are exact, so they do not depend on how the searches are split
+        Agent::init_agents(&mut p1_agent, &mut p2_agent, initial_state, || {
+            let mut negamax = crate::Negamax::new();
+            move |s, act, reward| {
+                reward.value = negamax.action_value(s, act);
+            }
+        });
 
         (p1_agent, p2_agent)
     }
@@ -258,11 +508,7 @@ impl Agent {
 
 impl std::fmt::Display for Agent {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let encoded_states = {
-            let mut v = self.0.keys().collect::<Vec<_>>();
-            v.sort_unstable();
-            v
-        };
+        let encoded_states = self.encoded_states();
         for (i, encoded_state) in encoded_states.iter().enumerate() {
             let optimal_actions = {
                 let mut v = self.optimal_actions(encoded_state);
@@ -283,10 +529,88 @@ impl std::fmt::Display for Agent {
                 }
                 write!(f, "}}")?;
                 if i != encoded_states.len() - 1 {
-                    writeln!(f, "")?;
+                    writeln!(f)?;
                 }
             }
         }
         Ok(())
     }
 }
+
+/// the values visited in part of an epoch of `Agent::train`, on top of the values of the previous epoch
+///
+/// note: keyed by canonical state-actions like the tables, see `Agent::reward`
+struct EpochValues<'a> {
+    agent: &'a Agent,
+    visited: HashMap<crate::StateAction, (crate::Value, bool)>, // the value so far, and whether it is updated
+}
+
+impl<'a> EpochValues<'a> {
+    fn new(agent: &'a Agent) -> EpochValues<'a> {
+        EpochValues {
+            agent,
+            visited: HashMap::new(),
+        }
+    }
+
+    fn canonical((encoded_state, action): &crate::StateAction) -> crate::StateAction {
+        let (canonical, symmetry) = encoded_state.canonical();
+        (canonical, symmetry.action(action))
+    }
+
+    /// the value of the state-action if already visited
+    fn visited_value(&self, state_action: &crate::StateAction) -> Option<crate::Value> {
+        self.visited
+            .get(&EpochValues::canonical(state_action))
+            .map(|(value, _)| *value)
+    }
+
+    /// like `Agent::optimal_actions`, but with the values visited so far
+    fn optimal_actions(&self, at_encoded_state: &crate::EncodedState) -> Vec<game::Action> {
+        let (canonical, symmetry) = at_encoded_state.canonical();
+        let values = self.agent.0[&canonical]
+            .iter()
+            .map(|(action, reward)| {
+                let value = self
+                    .visited
+                    .get(&(canonical, *action))
+                    .map_or(reward.value, |(value, _)| *value);
+                (action, value)
+            })
+            .collect::<Vec<_>>();
+        let max_value = values
+            .iter()
+            .map(|(_, value)| *value)
+            .max()
+            .unwrap_or_default();
+        let inverse = symmetry.inverse();
+        values
+            .into_iter()
+            .filter(|(_, value)| *value == max_value)
+            .map(|(action, _)| inverse.action(action))
+            .collect()
+    }
+
+    /// the first visit sets the value, later visits only raise it
+    ///
+    /// return `true` if the value is updated, `false` otherwise
+    fn visit(&mut self, state_action: &crate::StateAction, value: crate::Value) -> bool {
+        let (canonical, action) = EpochValues::canonical(state_action);
+        match self.visited.get_mut(&(canonical, action)) {
+            Some((curr_value, is_updated)) => {
+                if value > *curr_value {
+                    *curr_value = value;
+                    *is_updated = true;
+                    return true;
+                }
+                false
+            }
+            None => {
+                let is_updated = value != self.agent.0[&canonical][&action].value;
+                self.visited
+                    .insert((canonical, action), (value, is_updated));
+                is_updated
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use anyhow::Context;
+
+const USAGE: &str =
+    "usage: query [--pv] <position> [<player to act>] | query [--pv] --moves [<action>...]
+  e.g. `query 1,_,2,_ 2` or `query --moves 1 0` (played from the initial state),
+  where `--pv` also prints the principal variation";
+
+fn main() -> anyhow::Result<()> {
+    // note: logs go to stderr
+    env_logger::Builder::from_env(env_logger::Env::new().filter_or("SOLVER_LOG", "warn")).init();
+
+    // note: an invalid `START_POSITION` or `START_PLAYER` fails here, see `game::State::initial`
+    game::State::initial()?;
+
+    let mut args = std::env::args().skip(1).collect::<Vec<_>>();
+    // e.g. to see the line of play behind the value of the position
+    let with_principal_variation = args.first().is_some_and(|arg| arg == "--pv");
+    if with_principal_variation {
+        args.remove(0);
+    }
+    let state = parse_state(&args).with_context(|| format!("invalid query\n{USAGE}"))?;
+
+    // e.g. only whether the player to act can force a win on a board too large to solve
+    if let Some(proof_search) = *solver::PROOF_SEARCH {
+        anyhow::ensure!(
+            !with_principal_variation,
+            "a proof search gives no principal variation, query without `PROOF_SEARCH` instead"
+        );
+        let (proof, nodes) = match proof_search {
+            solver::ProofSearch::Pns => {
+                let mut pn_search = solver::PnSearch::new(*solver::PROOF_MAX_NODES);
+                (pn_search.prove(&state), pn_search.nodes())
+            }
+            solver::ProofSearch::DfPn => {
+                let mut df_pn = solver::DfPn::new(*solver::PROOF_MAX_NODES);
+                (df_pn.prove(&state), df_pn.nodes())
+            }
+        };
+        print_position(&state);
+        println!("proof: {proof}");
+        println!("nodes: {nodes}");
+        return Ok(());
+    }
+
+    // note: the value of every action needs an exhaustive engine, so `negamax` is the default rather than `trainer`,
+    // see `solver::Solution::ensure_exhaustive`
+    let (value, action_values, best_moves, principal_variation) =
+        if *solver::ENGINE == solver::Engine::Gaps {
+            let mut gap_solver = solver::GapSolver::new()?;
+            (
+                gap_solver.value(&state),
+                gap_solver.action_values(&state),
+                gap_solver.best_moves(&state),
+                with_principal_variation
+                    .then(|| gap_solver.principal_variation(&state))
+                    .flatten(),
+            )
+        } else {
+            let engine = match std::env::var("SOLVER_ENGINE") {
+                Ok(_) => *solver::ENGINE,
+                Err(_) => solver::Engine::Negamax,
+            };
+            let solution = solver::solve_with(engine, &state)?;
+            solution.ensure_exhaustive()?;
+            (
+                solution.evaluate(&state).unwrap(),
+                solution.action_values(&state).unwrap(),
+                solution.best_moves(&state).unwrap(),
+                with_principal_variation
+                    .then(|| solution.principal_variation(&state))
+                    .flatten(),
+            )
+        };
+
+    print_position(&state);
+    println!("value: {value}");
+    for (act, value) in action_values {
+        println!("action {act}: {value}");
+    }
+    println!(
+        "best: {}",
+        best_moves
+            .iter()
+            .map(|act| act.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    if with_principal_variation {
+        println!();
+        println!("principal variation:");
+        println!();
+        match principal_variation {
+            Some(principal_variation) => println!("{principal_variation}"),
+            // e.g. the game is already over at the position
+            None => println!("none"),
+        }
+    }
+    Ok(())
+}
+
+/// like the solved tables, e.g. `1,_,2,_`, then the player to act
+fn print_position(state: &game::State) {
+    println!("position: {}", solver::Position(state));
+    println!("player: {}", state.player_to_act);
+}
+
+/// a position with an optional player to act (player 1 by default), or the actions played from `game::INITIAL_STATE`
+///
+/// note: the turn of a position is worked out from its stone counts, so both forms agree on the same board, see
+/// `game::State::from_position`
+fn parse_state(args: &[String]) -> anyhow::Result<game::State> {
+    match args {
+        [flag, actions @ ..] if flag == "--moves" => {
+            let mut state = game::INITIAL_STATE.clone();
+            for s in actions {
+                let act = s
+                    .parse::<game::Action>()
+                    .and_then(|act| act.validate(&state))
+                    .with_context(|| format!("invalid action `{s}`"))?;
+                let (s_next, result) = state.step(&act);
+                anyhow::ensure!(
+                    matches!(result, game::Result::WaitingNextAction),
+                    "the game is already over after `{s}`"
+                );
+                state = s_next;
+            }
+            Ok(state)
+        }
+        [position] => game::State::from_position(position, game::Player::P1),
+        [position, player] => game::State::from_position(position, player.parse::<game::Player>()?),
+        _ => anyhow::bail!("expected a position or `--moves`"),
+    }
+}
@@ -0,0 +1,20 @@
+fn main() -> anyhow::Result<()> {
+    // e.g. `SOLVER_LOG=off` only prints the report
+    // note: logs go to stderr
+    env_logger::Builder::from_env(env_logger::Env::new().filter_or("SOLVER_LOG", "info")).init();
+
+    // note: an invalid `START_POSITION` or `START_PLAYER` fails here, see `game::State::initial`
+    game::State::initial()?;
+
+    let tournament = solver::Tournament::new();
+    let report = tournament.run()?;
+
+    println!();
+    println!(
+        "tournament ({} games per pairing, seed {}):",
+        tournament.games, tournament.seed
+    );
+    println!();
+    println!("{report}");
+    Ok(())
+}
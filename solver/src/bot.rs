@@ -0,0 +1,91 @@
+/// a computer player, e.g. parsed from `mcts:100`
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+    Copy,
+    Debug,
+    // hashable:
+    PartialEq,
+    Eq,
+    Hash,
+)]
+pub enum Bot {
+    Random,      // see `game::RandomPolicy`
+    Greedy,      // see `game::GreedyPolicy`
+    Solver,      // perfect play from `crate::solve`
+    Negamax,     // perfect play by `crate::Negamax`
+    Gaps,        // perfect play by `crate::GapSolver`, only on a single-row board
+    Depth(u32),  // see `crate::DepthLimited`, searching this many plies
+    Mcts(u32),   // see `crate::Mcts`, with this many iterations per move
+    DefaultMcts, // see `crate::Mcts`, with a budget of `crate::MCTS_BUDGET`
+}
+
+/// `random`, `greedy`, `solver`, `negamax`, `gaps`, `depth:<plies>`, `mcts:<iterations>` or `mcts`
+impl std::str::FromStr for Bot {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Bot> {
+        let positive = |val: &str| -> anyhow::Result<u32> {
+            let n = val.parse::<u32>()?;
+            anyhow::ensure!(n > 0, "`{s}` should be positive");
+            Ok(n)
+        };
+        match s.split_once(':') {
+            None => match s {
+                "random" => Ok(Bot::Random),
+                "greedy" => Ok(Bot::Greedy),
+                "solver" => Ok(Bot::Solver),
+                "negamax" => Ok(Bot::Negamax),
+                "gaps" => Ok(Bot::Gaps),
+                "mcts" => Ok(Bot::DefaultMcts),
+                _ => anyhow::bail!(
+                    "unknown bot `{s}`, expected `random`, `greedy`, `solver`, `negamax`, `gaps`, `depth:<plies>` or `mcts[:<iterations>]`"
+                ),
+            },
+            Some(("depth", val)) => Ok(Bot::Depth(positive(val)?)),
+            Some(("mcts", val)) => Ok(Bot::Mcts(positive(val)?)),
+            Some(_) => anyhow::bail!("unknown bot `{s}`, expected `depth:<plies>` or `mcts:<iterations>`"),
+        }
+    }
+}
+
+/// the same notation as parsed
+impl std::fmt::Display for Bot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Bot::Random => write!(f, "random"),
+            Bot::Greedy => write!(f, "greedy"),
+            Bot::Solver => write!(f, "solver"),
+            Bot::Negamax => write!(f, "negamax"),
+            Bot::Gaps => write!(f, "gaps"),
+            Bot::Depth(plies) => write!(f, "depth:{plies}"),
+            Bot::Mcts(iterations) => write!(f, "mcts:{iterations}"),
+            Bot::DefaultMcts => write!(f, "mcts"),
+        }
+    }
+}
+
+impl Bot {
+    /// a new player, where `seed` only matters to bots breaking ties or searching randomly
+    ///
+    /// note: `Bot::Solver` solves from `game::INITIAL_STATE` first, unless `crate::ENGINE` is `Engine::Gaps`,
+    /// which has no tables and plays like `Bot::Gaps`
+    pub fn new_policy(&self, seed: u64) -> anyhow::Result<Box<dyn game::Policy>> {
+        Ok(match *self {
+            Bot::Random => Box::new(game::RandomPolicy::new(seed)),
+            Bot::Greedy => Box::new(game::GreedyPolicy::new(seed)),
+            Bot::Solver if *crate::ENGINE == crate::Engine::Gaps => {
+                Box::new(crate::GapSolver::new()?)
+            }
+            Bot::Solver => Box::new(crate::solve(&game::INITIAL_STATE)?),
+            Bot::Negamax => Box::new(crate::Negamax::new()),
+            Bot::Gaps => Box::new(crate::GapSolver::new()?),
+            Bot::Depth(plies) => Box::new(crate::DepthLimited::new(plies, seed)),
+            Bot::Mcts(iterations) => Box::new(crate::Mcts::new(
+                crate::Budget::Iterations(iterations),
+                seed,
+            )),
+            Bot::DefaultMcts => Box::new(crate::Mcts::new(*crate::MCTS_BUDGET, seed)),
+        })
+    }
+}
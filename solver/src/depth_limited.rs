@@ -0,0 +1,44 @@
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// a negamax search cut off after `depth` plies, e.g. a weaker opponent in a tournament, see
+/// `crate::Negamax::new_depth_limited`
+pub struct DepthLimited {
+    pub depth: u32,
+    negamax: crate::Negamax,
+    rng: rand::rngs::StdRng, // to break ties between equally good actions
+}
+
+impl DepthLimited {
+    pub fn new(depth: u32, seed: u64) -> DepthLimited {
+        DepthLimited {
+            depth,
+            negamax: crate::Negamax::new_depth_limited(depth),
+            rng: rand::rngs::StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// the value of taking `action` at `state` as seen within the horizon
+    pub fn action_value(&mut self, state: &game::State, action: &game::Action) -> crate::Value {
+        self.negamax.action_value(state, action)
+    }
+}
+
+/// a random one of the best actions within the horizon
+impl game::Policy for DepthLimited {
+    fn choose(&mut self, state: &game::State) -> game::Action {
+        let mut action_values = state
+            .all_actions()
+            .into_iter()
+            .map(|act| (act, self.action_value(state, &act)))
+            .collect::<Vec<_>>();
+        action_values.sort_unstable_by_key(|(act, _)| *act);
+        let max_value = action_values.iter().map(|(_, value)| *value).max();
+        let best_actions = action_values
+            .into_iter()
+            .filter(|(_, value)| Some(*value) == max_value)
+            .map(|(act, _)| act)
+            .collect::<Vec<_>>();
+        *best_actions.choose(&mut self.rng).unwrap()
+    }
+}
@@ -1,20 +1,61 @@
-use bimap::BiMap;
-use fixedbitset::FixedBitSet;
 use once_cell::sync::Lazy;
 
-static N: Lazy<usize> = Lazy::new(|| 2 * *game::BOARD_SIZE);
+/// number of bits needed to store any value in `0..=max`
+fn bits_for(max: usize) -> usize {
+    (usize::BITS - max.leading_zeros()) as usize
+}
+
+// the least significant bits store the turn progress, i.e. (player_to_act, stones_left, turn, passes)
+static PASSES_BITS: Lazy<usize> = Lazy::new(|| {
+    if *game::ALLOW_PASS {
+        // note: the game is over once all players pass
+        bits_for(game::PLAYER_COUNT - 1)
+    } else {
+        0
+    }
+});
+static TURN_BITS: Lazy<usize> = Lazy::new(|| bits_for(game::TURN_SCHEDULE.len() - 1));
+static STONES_LEFT_BITS: Lazy<usize> =
+    Lazy::new(|| bits_for(*game::TURN_SCHEDULE.iter().max().unwrap()));
+static TURN_PROGRESS_BITS: Lazy<usize> =
+    Lazy::new(|| *PASSES_BITS + *TURN_BITS + *STONES_LEFT_BITS + 1);
 
-static PLAYER_ENCODING: Lazy<BiMap<Option<game::Player>, (bool, bool)>> = Lazy::new(|| {
-    BiMap::from_iter([
-        (None, (false, false) /* 00 */),
-        (Some(game::Player::P1), (false, true) /* 01 */),
-        (Some(game::Player::P2), (true, false) /* 10 */),
-    ])
+static N: Lazy<usize> = Lazy::new(|| {
+    let n = 2 * game::BOARD.cells() + *TURN_PROGRESS_BITS;
+    assert!(n <= 64 * WIDE_WORDS, "{}", too_large(n));
+    n
 });
 
+const WIDE_WORDS: usize = 4;
+
+fn too_large(n: usize) -> String {
+    format!(
+        "the board is too large to be encoded ({n} bits, at most {}), only `SOLVER_ENGINE=gaps` or `PROOF_SEARCH` can \
+         search it",
+        64 * WIDE_WORDS
+    )
+}
+
+/// each cell takes 2 bits, i.e. None (00) < Some(P1) (01) < Some(P2) (10)
+fn encode_cell(cell: &game::Cell) -> u64 {
+    match cell {
+        None => 0b00,
+        Some(game::Player::P1) => 0b01,
+        Some(game::Player::P2) => 0b10,
+    }
+}
+
+/// the offset of the 2 bits of `state.board[i]`
+///
+/// note: state.board[0] is the most significant so that the ordering is more intuitive
+fn cell_offset(i: usize) -> usize {
+    *N - 2 * (i + 1)
+}
+
 #[derive(
     // sane defaults for value objects:
     Clone,
+    Copy,
     Debug,
     // hashable:
     PartialEq,
@@ -23,44 +64,187 @@ static PLAYER_ENCODING: Lazy<BiMap<Option<game::Player>, (bool, bool)>> = Lazy::
     // sortable:
     PartialOrd,
     Ord,
+    // serializable:
+    serde::Serialize,
+    serde::Deserialize,
 )]
-pub struct EncodedState(
-    pub FixedBitSet, // note: FixedBitSet[0] is the least significant bit
-);
+pub enum EncodedState {
+    Narrow(u64),
+    // OPTIMIZATION: only used when the board does not fit in a single word
+    Wide([u64; WIDE_WORDS]), // note: the most significant word comes first
+}
 
 impl EncodedState {
+    /// fails if the states of the configured board are too large to be encoded, rather than panicking at the first
+    /// encoded state
+    pub fn ensure_fits() -> anyhow::Result<()> {
+        let n = 2 * game::BOARD.cells() + *TURN_PROGRESS_BITS;
+        anyhow::ensure!(n <= 64 * WIDE_WORDS, too_large(n));
+        Ok(())
+    }
+
     pub fn new(state: &game::State) -> EncodedState {
-        let mut bitset = FixedBitSet::with_capacity(*N);
-        for (i, player) in state.board.iter().enumerate() {
-            let (bit1, bit2) = *PLAYER_ENCODING.get_by_left(&player).unwrap();
-            // updating bitset from the most significant bit so that the ordering is more intuitive,
-            // i.e. None < Some(P1) < Some(P2), and state.board[0] is the most significant bit
-            bitset.set((*N - 1) - (2 * i), bit1);
-            bitset.set((*N - 1) - (2 * i + 1), bit2);
+        let mut encoded_state = EncodedState::zero();
+        for (i, cell) in state.board.iter().enumerate() {
+            encoded_state.set(cell_offset(i), 2, encode_cell(cell));
+        }
+
+        let mut offset = 0;
+        for (val, bits) in [
+            (state.passes, *PASSES_BITS),
+            (state.turn, *TURN_BITS),
+            (state.stones_left, *STONES_LEFT_BITS),
+            (state.player_to_act as usize, 1),
+        ] {
+            encoded_state.set(offset, bits, val as u64);
+            offset += bits;
+        }
+
+        encoded_state
+    }
+
+    fn zero() -> EncodedState {
+        if *N <= 64 {
+            EncodedState::Narrow(0)
+        } else {
+            EncodedState::Wide([0; WIDE_WORDS])
+        }
+    }
+
+    /// read `bits` (at most 64) bits from `offset`, where bit 0 is the least significant bit
+    fn get(&self, offset: usize, bits: usize) -> u64 {
+        if bits == 0 {
+            return 0;
+        }
+        match self {
+            EncodedState::Narrow(word) => (word >> offset) & (u64::MAX >> (64 - bits)),
+            EncodedState::Wide(words) => (0..bits)
+                .map(|bit| {
+                    let i = offset + bit;
+                    ((words[WIDE_WORDS - 1 - i / 64] >> (i % 64)) & 1) << bit
+                })
+                .sum(),
         }
-        EncodedState(bitset)
     }
+
+    /// write `bits` (at most 64) bits of `val` to `offset`, see `EncodedState::get`
+    fn set(&mut self, offset: usize, bits: usize, val: u64) {
+        match self {
+            EncodedState::Narrow(word) => {
+                for bit in 0..bits {
+                    let mask = 1 << (offset + bit);
+                    *word = (*word & !mask) | (((val >> bit) & 1) << (offset + bit));
+                }
+            }
+            EncodedState::Wide(words) => {
+                for bit in 0..bits {
+                    let i = offset + bit;
+                    let word = &mut words[WIDE_WORDS - 1 - i / 64];
+                    let mask = 1 << (i % 64);
+                    *word = (*word & !mask) | (((val >> bit) & 1) << (i % 64));
+                }
+            }
+        }
+    }
+
+    /// the same state with the board transformed by `symmetry`
+    pub fn transformed(&self, symmetry: &game::Symmetry) -> EncodedState {
+        let mut encoded_state = *self;
+        for i in 0..game::BOARD.cells() {
+            encoded_state.set(
+                cell_offset(symmetry.cell(i)),
+                2,
+                self.get(cell_offset(i), 2),
+            );
+        }
+        encoded_state
+    }
+
+    /// the smallest encoding among all symmetric states, and the symmetry transforming this state into it
+    ///
+    /// note: a canonical state is canonical by the identity, see `game::Board::symmetries`
+    pub fn canonical(&self) -> (EncodedState, &'static game::Symmetry) {
+        game::BOARD
+            .symmetries
+            .iter()
+            .map(|symmetry| (self.transformed(symmetry), symmetry))
+            .min_by(|(a, _), (b, _)| a.cmp(b))
+            .unwrap()
+    }
+
+    pub fn player_to_act(&self) -> game::Player {
+        if self.get(*TURN_PROGRESS_BITS - 1, 1) == 1 {
+            game::Player::P2
+        } else {
+            game::Player::P1
+        }
+    }
+
+    pub fn passes(&self) -> usize {
+        self.get(0, *PASSES_BITS) as usize
+    }
+
+    pub fn stones_left(&self) -> usize {
+        self.get(*PASSES_BITS + *TURN_BITS, *STONES_LEFT_BITS) as usize
+    }
+}
+
+/// the cells like `game::State::from_position`, e.g. `1,_,2,_`, then the stones left in the turn and whether the last
+/// player passed, when worth showing
+fn fmt_position(
+    f: &mut std::fmt::Formatter<'_>,
+    board: &[game::Cell],
+    stones_left: usize,
+    passes: usize,
+) -> std::fmt::Result {
+    let cells = board
+        .iter()
+        .map(|cell| match cell {
+            Some(game::Player::P1) => "1",
+            Some(game::Player::P2) => "2",
+            None => "_",
+        })
+        .collect::<Vec<_>>();
+    write!(
+        f,
+        "{}",
+        cells
+            .chunks(game::BOARD.cols)
+            .map(|row| row.join(","))
+            .collect::<Vec<_>>()
+            .join("/")
+    )?;
+    // only worth showing when a turn can have more than 1 stone
+    if game::TURN_SCHEDULE.iter().any(|&n| n > 1) {
+        write!(f, " +{stones_left}")?;
+    }
+    // only worth showing when a pass is allowed
+    if passes > 0 {
+        write!(f, " (passed)")?;
+    }
+    Ok(())
 }
 
 impl std::fmt::Display for EncodedState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            (0..*game::BOARD_SIZE)
-                .into_iter()
-                .map(|i| {
-                    match PLAYER_ENCODING
-                        .get_by_right(&(self.0[(*N - 1) - (2 * i)], self.0[(*N - 1) - (2 * i + 1)]))
-                        .unwrap()
-                    {
-                        Some(game::Player::P1) => "1",
-                        Some(game::Player::P2) => "2",
-                        None => "_",
-                    }
-                })
-                .collect::<Vec<_>>()
-                .join(",")
-        )
+        let board = (0..game::BOARD.cells())
+            .map(|i| match self.get(cell_offset(i), 2) {
+                0b01 => Some(game::Player::P1),
+                0b10 => Some(game::Player::P2),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        fmt_position(f, &board, self.stones_left(), self.passes())
+    }
+}
+
+/// a state printed like its `EncodedState`, e.g. `1,_,2,_`
+///
+/// note: not encoded, so that any board size can be printed, e.g. by `GapSolver` or `DfPn`
+pub struct Position<'a>(pub &'a game::State);
+
+impl std::fmt::Display for Position<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_position(f, &self.0.board, self.0.stones_left, self.0.passes)
     }
 }
@@ -0,0 +1,102 @@
+use std::io::{BufWriter, Write};
+
+/// whether an action wins, draws or loses, see `crate::Value`
+#[derive(
+    // sane defaults for unit-like enums:
+    Clone,
+    Copy,
+    derive_more::Display,
+    Debug,
+    // hashable:
+    PartialEq,
+    Eq,
+    Hash,
+    // serializable:
+    serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    #[display(fmt = "win")]
+    Win,
+    #[display(fmt = "draw")]
+    Draw,
+    #[display(fmt = "lose")]
+    Lose,
+}
+
+/// one action at one state of the solved tables, see `Solution::table_rows`
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+    Debug,
+    // serializable:
+    serde::Serialize,
+)]
+pub struct TableRow {
+    pub state: String,        // canonical, like the solved tables, e.g. `_,1,_,_`
+    pub player: game::Player, // to act at `state`
+    pub action: String,       // e.g. `(0)`
+    pub result: Outcome,
+    pub plies: Option<u32>, // until the result, e.g. `3` for `WIN(3)`
+    pub value: String,      // e.g. `WIN(3)`
+    pub optimal: bool,      // whether `value` is the best at `state`
+}
+
+impl crate::Solution {
+    /// a row per action of every state in the solved tables of player 1 then player 2, sorted like the tables
+    ///
+    /// fails unless solved by an exhaustive engine, see `Solution::ensure_exhaustive`
+    pub fn table_rows(&self) -> anyhow::Result<Vec<TableRow>> {
+        self.ensure_exhaustive()?;
+        let mut rows = vec![];
+        for agent in [&self.p1_agent, &self.p2_agent] {
+            for encoded_state in agent.encoded_states() {
+                let max_value = agent.max_value(encoded_state);
+                let mut actions = agent.actions(encoded_state);
+                actions.sort_unstable();
+                for act in actions {
+                    let value = agent.reward(encoded_state, &act).value;
+                    let (result, plies) = match value {
+                        crate::Value::LOSE(plies) => (Outcome::Lose, Some(plies)),
+                        crate::Value::DRAW => (Outcome::Draw, None),
+                        crate::Value::WIN(plies) => (Outcome::Win, Some(plies)),
+                        crate::Value::UNDEFINED => {
+                            anyhow::bail!("no value of {act} at {encoded_state}")
+                        }
+                    };
+                    rows.push(TableRow {
+                        state: encoded_state.to_string(),
+                        player: encoded_state.player_to_act(),
+                        action: act.to_string(),
+                        result,
+                        plies,
+                        value: value.to_string(),
+                        optimal: value == max_value,
+                    });
+                }
+            }
+        }
+        Ok(rows)
+    }
+
+    /// save `Solution::table_rows` as CSV, with a header row of the field names
+    pub fn save_csv(&self, path: impl AsRef<std::path::Path>) -> anyhow::Result<()> {
+        let rows = self.table_rows()?;
+        let mut writer = csv::Writer::from_path(path)?;
+        for row in rows {
+            writer.serialize(row)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// save `Solution::table_rows` as a JSON array of objects, keyed by the field names like `Solution::save_csv`
+    pub fn save_json(&self, path: impl AsRef<std::path::Path>) -> anyhow::Result<()> {
+        let rows = self.table_rows()?;
+        let mut writer = BufWriter::new(std::fs::File::create(path)?);
+        serde_json::to_writer_pretty(&mut writer, &rows)?;
+        writeln!(writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
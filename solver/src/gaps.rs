@@ -0,0 +1,395 @@
+use std::collections::HashMap;
+
+/// what borders a gap, relative to the player to act
+#[derive(
+    // sane defaults for unit-like enums:
+    Clone,
+    Copy,
+    Debug,
+    // hashable:
+    PartialEq,
+    Eq,
+    Hash,
+    // sortable:
+    PartialOrd,
+    Ord,
+)]
+enum Border {
+    Wall,
+    Own,
+    Opponent,
+}
+
+impl Border {
+    fn new(cell: &game::Cell, player_to_act: game::Player) -> Border {
+        match cell {
+            None => unreachable!("a gap is bordered by stones or walls"),
+            Some(player) if *player == player_to_act => Border::Own,
+            Some(_) => Border::Opponent,
+        }
+    }
+
+    /// the same border seen by the opponent
+    fn opposite(&self) -> Border {
+        match *self {
+            Border::Wall => Border::Wall,
+            Border::Own => Border::Opponent,
+            Border::Opponent => Border::Own,
+        }
+    }
+}
+
+/// a maximal run of empty cells
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+    Copy,
+    Debug,
+    // hashable:
+    PartialEq,
+    Eq,
+    Hash,
+    // sortable:
+    PartialOrd,
+    Ord,
+)]
+struct Gap {
+    left: Border,
+    len: usize,
+    right: Border,
+}
+
+impl Gap {
+    /// note: a gap and its mirror image are the same gap, so `left <= right`
+    fn new(left: Border, len: usize, right: Border) -> Gap {
+        Gap {
+            left: left.min(right),
+            len,
+            right: left.max(right),
+        }
+    }
+
+    fn opposite(&self) -> Gap {
+        Gap::new(self.left.opposite(), self.len, self.right.opposite())
+    }
+}
+
+/// a placement in a gap, or passing
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+    Copy,
+    Debug,
+    // hashable:
+    PartialEq,
+    Eq,
+    Hash,
+)]
+enum Move {
+    Place { gap: Gap, at: usize }, // `at` counts from the left border of `gap`
+    Pass,
+}
+
+/// a position of a single-row board, reduced to the multiset of its gaps and the progress of the current turn
+///
+/// note: stones not bordering any gap can never matter again, and the rules are the same for both players,
+/// so positions with the same gaps relative to the player to act share the same value
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+    Debug,
+    // hashable:
+    PartialEq,
+    Eq,
+    Hash,
+)]
+struct GapPosition {
+    gaps: Vec<Gap>, // sorted
+    stones_left: usize,
+    turn: usize,
+    passes: usize,
+}
+
+impl GapPosition {
+    fn new(state: &game::State) -> GapPosition {
+        let board = &state.board;
+        let mut gaps = vec![];
+        let mut i = 0;
+        while i < board.len() {
+            if board[i].is_some() {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < board.len() && board[i].is_none() {
+                i += 1;
+            }
+            let left = match start {
+                0 => Border::Wall,
+                _ => Border::new(&board[start - 1], state.player_to_act),
+            };
+            let right = match i {
+                _ if i == board.len() => Border::Wall,
+                _ => Border::new(&board[i], state.player_to_act),
+            };
+            gaps.push(Gap::new(left, i - start, right));
+        }
+        gaps.sort_unstable();
+        GapPosition {
+            gaps,
+            stones_left: state.stones_left,
+            turn: state.turn,
+            passes: state.passes,
+        }
+    }
+
+    /// all moves up to mirroring a gap, where moves in the same kind of gap are only listed once
+    fn all_moves(&self) -> Vec<Move> {
+        let mut moves = vec![];
+        for (i, gap) in self.gaps.iter().enumerate() {
+            if i > 0 && self.gaps[i - 1] == *gap {
+                continue;
+            }
+            let len = if gap.left == gap.right {
+                gap.len.div_ceil(2)
+            } else {
+                gap.len
+            };
+            moves.extend((0..len).map(|at| Move::Place { gap: *gap, at }));
+        }
+        if *game::ALLOW_PASS {
+            moves.push(Move::Pass);
+        }
+        moves
+    }
+
+    /// the same as `game::State::step`, plus whether the player to act changes
+    ///
+    /// note: the next position is relative to its own player to act
+    fn step(&self, mv: &Move) -> (GapPosition, game::Result, bool) {
+        let mut new_position = self.clone();
+        let is_win = match *mv {
+            Move::Place { gap, at } => {
+                let i = new_position.gaps.iter().position(|g| *g == gap).unwrap();
+                new_position.gaps.swap_remove(i);
+                if at > 0 {
+                    new_position.gaps.push(Gap::new(gap.left, at, Border::Own));
+                }
+                if at < gap.len - 1 {
+                    new_position
+                        .gaps
+                        .push(Gap::new(Border::Own, gap.len - 1 - at, gap.right));
+                }
+                new_position.stones_left -= 1;
+                new_position.passes = 0;
+                (at == 0 && gap.left == Border::Own)
+                    || (at == gap.len - 1 && gap.right == Border::Own)
+            }
+            Move::Pass => {
+                new_position.stones_left = 0;
+                new_position.passes += 1;
+                false
+            }
+        };
+        let is_next_player = new_position.stones_left == 0;
+        if is_next_player {
+            new_position.turn = (self.turn + 1).min(game::TURN_SCHEDULE.len() - 1);
+            new_position.stones_left = game::TURN_SCHEDULE[new_position.turn];
+            new_position.gaps = new_position.gaps.iter().map(Gap::opposite).collect();
+        }
+        new_position.gaps.sort_unstable();
+
+        let result = if is_win {
+            game::Result::Win
+        } else if new_position.gaps.is_empty() || new_position.passes == game::PLAYER_COUNT {
+            game::Result::Draw
+        } else {
+            game::Result::WaitingNextAction
+        };
+
+        (new_position, result, is_next_player)
+    }
+}
+
+struct Entry {
+    value: crate::Value,
+    bound: crate::Bound,
+    // OPTIMIZATION: searched first when the position is visited again
+    best_move: Option<Move>,
+}
+
+/// negamax search on single-row boards, memoised on the gaps of a position instead of the whole board,
+/// see `GapPosition`
+///
+/// OPTIMIZATION: far fewer positions than `crate::Negamax` visits, so boards of hundreds of cells can be solved
+#[derive(Default)]
+pub struct GapSolver {
+    transposition_table: HashMap<GapPosition, Entry>,
+}
+
+impl GapSolver {
+    /// fails if the board is not a single row, see `game::BOARD`
+    pub fn new() -> anyhow::Result<GapSolver> {
+        let is_row = (0..game::BOARD.cells()).all(|i| {
+            let neighbours = &game::BOARD.neighbours[i];
+            neighbours.len() == usize::from(i > 0) + usize::from(i + 1 < game::BOARD.cells())
+                && neighbours.iter().all(|&j| j + 1 == i || i + 1 == j)
+        });
+        anyhow::ensure!(is_row, "the gap solver only supports a single-row board");
+        Ok(GapSolver::default())
+    }
+
+    /// the exact value of `state`
+    pub fn value(&mut self, state: &game::State) -> crate::Value {
+        self.search(
+            &GapPosition::new(state),
+            crate::Value::MIN,
+            crate::Value::MAX,
+        )
+    }
+
+    /// the exact value of taking `action` at `state`
+    pub fn action_value(&mut self, state: &game::State, action: &game::Action) -> crate::Value {
+        let (s_next, result) = state.step(action);
+        match result {
+            game::Result::Win => crate::Value::WIN(1),
+            game::Result::Draw => crate::Value::DRAW,
+            game::Result::WaitingNextAction => {
+                let value = self.value(&s_next);
+                if s_next.player_to_act == state.player_to_act {
+                    value.later(1)
+                } else {
+                    value.opposite().later(1)
+                }
+            }
+        }
+    }
+
+    /// the exact value of every legal action at `state`, sorted by action
+    pub fn action_values(&mut self, state: &game::State) -> Vec<(game::Action, crate::Value)> {
+        let mut v = state
+            .all_actions()
+            .into_iter()
+            .map(|act| (act, self.action_value(state, &act)))
+            .collect::<Vec<_>>();
+        v.sort_unstable_by_key(|(act, _)| *act);
+        v
+    }
+
+    /// the optimal actions at `state`, sorted
+    pub fn best_moves(&mut self, state: &game::State) -> Vec<game::Action> {
+        let action_values = self.action_values(state);
+        let max_value = action_values.iter().map(|(_, value)| *value).max();
+        action_values
+            .into_iter()
+            .filter(|(_, value)| Some(*value) == max_value)
+            .map(|(act, _)| act)
+            .collect()
+    }
+
+    fn search(
+        &mut self,
+        position: &GapPosition,
+        mut alpha: crate::Value,
+        mut beta: crate::Value,
+    ) -> crate::Value {
+        // OPTIMIZATION: nothing beats winning right now, i.e. placing next to an own stone
+        if position
+            .gaps
+            .iter()
+            .any(|gap| gap.left == Border::Own || gap.right == Border::Own)
+        {
+            return crate::Value::WIN(1);
+        }
+        // note: the result cannot be reached in 0 plies, so no value is better than winning in 1 ply
+        beta = beta.min(crate::Value::WIN(1));
+        let orig_alpha = alpha;
+
+        let mut hint = None;
+        if let Some(entry) = self.transposition_table.get(position) {
+            match entry.bound {
+                crate::Bound::Exact => return entry.value,
+                crate::Bound::Lower => alpha = alpha.max(entry.value),
+                crate::Bound::Upper => beta = beta.min(entry.value),
+            }
+            if alpha >= beta {
+                return entry.value;
+            }
+            hint = entry.best_move;
+        }
+
+        let mut best_value = crate::Value::new();
+        let mut best_move = None;
+        for mv in GapSolver::ordered_moves(position, hint) {
+            let value = self.search_move(position, &mv, alpha, beta);
+            if value > best_value {
+                best_value = value;
+                best_move = Some(mv);
+            }
+            alpha = alpha.max(value);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        self.transposition_table.insert(
+            position.clone(),
+            Entry {
+                value: best_value,
+                bound: if best_value <= orig_alpha {
+                    crate::Bound::Upper
+                } else if best_value >= beta {
+                    crate::Bound::Lower
+                } else {
+                    crate::Bound::Exact
+                },
+                best_move,
+            },
+        );
+        best_value
+    }
+
+    fn search_move(
+        &mut self,
+        position: &GapPosition,
+        mv: &Move,
+        alpha: crate::Value,
+        beta: crate::Value,
+    ) -> crate::Value {
+        let (p_next, result, is_next_player) = position.step(mv);
+        match result {
+            game::Result::Win => crate::Value::WIN(1),
+            game::Result::Draw => crate::Value::DRAW,
+            game::Result::WaitingNextAction => {
+                let (alpha, beta) = crate::Value::window_through(alpha, beta, !is_next_player);
+                self.search(&p_next, alpha, beta).through(!is_next_player)
+            }
+        }
+    }
+
+    /// the best move found before (if any) goes first, then moves next to the opponent's stones (likely blocking),
+    /// then moves leaving empty cells on both sides (likely threatening twice), and passing goes last
+    fn ordered_moves(position: &GapPosition, hint: Option<Move>) -> Vec<Move> {
+        let mut moves = position.all_moves();
+        moves.sort_by_cached_key(|mv| {
+            if Some(*mv) == hint {
+                return 0;
+            }
+            match *mv {
+                Move::Place { gap, at } => {
+                    if (at == 0 && gap.left == Border::Opponent)
+                        || (at == gap.len - 1 && gap.right == Border::Opponent)
+                    {
+                        1
+                    } else if at > 0 && at < gap.len - 1 {
+                        2
+                    } else {
+                        3
+                    }
+                }
+                Move::Pass => 4,
+            }
+        });
+        moves
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+/// a node of a `StateGraph`
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+)]
+pub enum GraphNode {
+    State {
+        state: game::State,
+        value: Option<crate::Value>, // of `state` for the player to act, `None` if not in the solution
+    },
+    GameOver {
+        state: game::State,
+        winner: Option<game::Player>, // `None` for a draw
+    },
+}
+
+/// an action from `nodes[from]` to `nodes[to]` of a `StateGraph`
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+    Copy,
+    Debug,
+)]
+pub struct GraphEdge {
+    pub from: usize,
+    pub to: usize,
+    pub action: game::Action,
+    pub is_optimal: bool, // one of the best moves of the player to act at `nodes[from]`
+}
+
+/// the positions reachable from some position with the solved values, e.g. rendered by Graphviz via the DOT format
+///
+/// note: unlike the solved tables, symmetric positions are different nodes, so that every action reads the same as
+/// in the game
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+)]
+pub struct StateGraph {
+    pub nodes: Vec<GraphNode>, // the initial position comes first
+    pub edges: Vec<GraphEdge>,
+}
+
+impl crate::Solution {
+    /// every position reachable from `initial_state` (or only under optimal play by both players if `optimal_only`),
+    /// where a position not in the solution is a leaf, see `Solution::evaluate`
+    pub fn state_graph(&self, initial_state: &game::State, optimal_only: bool) -> StateGraph {
+        let mut nodes = vec![];
+        let mut edges = vec![];
+        let mut ids = HashMap::new();
+        let mut game_over_ids = HashMap::new();
+
+        ids.insert(crate::EncodedState::new(initial_state), 0);
+        nodes.push(GraphNode::State {
+            state: initial_state.clone(),
+            value: self.evaluate(initial_state),
+        });
+        // BFS, where the ids are in the order of discovery
+        let mut i = 0;
+        while i < nodes.len() {
+            let GraphNode::State { state: s, .. } = nodes[i].clone() else {
+                i += 1;
+                continue;
+            };
+            let Some(best_moves) = self.best_moves(&s) else {
+                i += 1;
+                continue;
+            };
+            let mut actions = s.all_actions();
+            actions.sort_unstable();
+            for act in actions {
+                let is_optimal = best_moves.contains(&act);
+                if optimal_only && !is_optimal {
+                    continue;
+                }
+                let (s_next, result) = s.step(&act);
+                let encoded_state = crate::EncodedState::new(&s_next);
+                let to = match result {
+                    game::Result::WaitingNextAction => {
+                        *ids.entry(encoded_state).or_insert_with(|| {
+                            nodes.push(GraphNode::State {
+                                value: self.evaluate(&s_next),
+                                state: s_next,
+                            });
+                            nodes.len() - 1
+                        })
+                    }
+                    // note: kept apart from the other positions, since the same board can be over or not
+                    // depending on how it is reached, e.g. by passing
+                    game::Result::Win | game::Result::Draw => {
+                        *game_over_ids.entry(encoded_state).or_insert_with(|| {
+                            nodes.push(GraphNode::GameOver {
+                                winner: matches!(result, game::Result::Win)
+                                    .then_some(s.player_to_act),
+                                state: s_next,
+                            });
+                            nodes.len() - 1
+                        })
+                    }
+                };
+                edges.push(GraphEdge {
+                    from: i,
+                    to,
+                    action: act,
+                    is_optimal,
+                });
+            }
+            i += 1;
+        }
+
+        StateGraph { nodes, edges }
+    }
+}
+
+/// the DOT format, where a position is labelled like the solved tables, e.g. `P1 _,_,_,_ [WIN(3)]`, a finished game
+/// by its result, and the optimal actions are highlighted
+impl std::fmt::Display for StateGraph {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "digraph {{")?;
+        writeln!(f, "    node [shape=box, fontname=\"monospace\"];")?;
+        for (i, node) in self.nodes.iter().enumerate() {
+            match node {
+                GraphNode::State { state, value } => {
+                    let value = value.map_or("?".to_string(), |value| value.to_string());
+                    writeln!(
+                        f,
+                        "    {i} [label=\"{} {}\\n[{value}]\"];",
+                        state.player_to_act,
+                        crate::EncodedState::new(state)
+                    )?;
+                }
+                GraphNode::GameOver { state, winner } => {
+                    let result =
+                        winner.map_or("draw".to_string(), |player| format!("{player} wins"));
+                    writeln!(
+                        f,
+                        "    {i} [label=\"{}\\n{result}\", shape=ellipse];",
+                        crate::EncodedState::new(state)
+                    )?;
+                }
+            }
+        }
+        for edge in self.edges.iter() {
+            write!(
+                f,
+                "    {} -> {} [label=\"{}\"",
+                edge.from, edge.to, edge.action
+            )?;
+            if edge.is_optimal {
+                write!(f, ", color=red, penwidth=2")?;
+            }
+            writeln!(f, "];")?;
+        }
+        write!(f, "}}")
+    }
+}
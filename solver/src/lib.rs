@@ -0,0 +1,44 @@
+mod agent;
+mod bot;
+mod depth_limited;
+mod encoding;
+mod export;
+mod gaps;
+mod graph;
+mod mcts;
+mod negamax;
+mod policy;
+mod proof_number;
+mod qlearning;
+mod retrograde;
+mod reward;
+mod solution;
+mod storage;
+mod tournament;
+mod training;
+mod variation;
+pub use agent::Agent;
+pub use bot::Bot;
+pub use depth_limited::DepthLimited;
+pub use encoding::{EncodedState, Position};
+pub use export::{Outcome, TableRow};
+pub use gaps::GapSolver;
+pub use graph::{GraphEdge, GraphNode, StateGraph};
+pub use mcts::{Budget, Mcts, MctsReport, MCTS_BUDGET, MCTS_SEED};
+pub use negamax::Negamax;
+pub use proof_number::{DfPn, PnSearch, Proof, ProofSearch, PROOF_MAX_NODES, PROOF_SEARCH};
+pub use qlearning::{Hyperparameters, QLearning, QLEARNING};
+pub use retrograde::Retrograde;
+pub use reward::Value;
+pub use solution::{solve, solve_with, Comparison, Engine, Solution, ENGINE, MAX_EPOCHS, THREADS};
+pub use tournament::{
+    Rating, Record, Tournament, TournamentReport, TOURNAMENT_BOTS, TOURNAMENT_GAMES,
+    TOURNAMENT_SEED,
+};
+pub use training::{EpochStats, TrainingReport};
+pub use variation::{PrincipalVariation, VariationPly};
+
+// internal to the agents and the engines filling their tables:
+pub(crate) use agent::StateAction;
+pub(crate) use negamax::Bound;
+pub(crate) use reward::Reward;
@@ -1,16 +1,140 @@
-mod agent;
-mod encoding;
-mod reward;
-use agent::*;
-use encoding::*;
-use reward::*;
-
-fn main() {
-    let (p1_agent, p2_agent) = Agent::new_trained();
-    println!("");
+use anyhow::Context;
+
+fn main() -> anyhow::Result<()> {
+    // e.g. `SOLVER_LOG=off` only prints the solved tables, `SOLVER_LOG=solver::backtrack=trace` also every trajectory
+    // note: logs go to stderr
+    env_logger::Builder::from_env(env_logger::Env::new().filter_or("SOLVER_LOG", "info")).init();
+
+    // note: an invalid `START_POSITION` or `START_PLAYER` fails here, see `game::State::initial`
+    game::State::initial()?;
+
+    if *solver::ENGINE == solver::Engine::Gaps {
+        return print_initial_state_solved_by_gaps();
+    }
+
+    let solution = match std::env::var("LOAD_SOLUTION") {
+        Ok(path) => {
+            let solution =
+                solver::Solution::load(&path).with_context(|| format!("failed to load {path}"))?;
+            anyhow::ensure!(
+                solution.initial_state == *game::INITIAL_STATE,
+                "the solution in {path} is solved from {} with {} to act, unlike `START_POSITION` and `START_PLAYER`",
+                solver::Position(&solution.initial_state),
+                solution.initial_state.player_to_act
+            );
+            solution
+        }
+        Err(_) => solver::solve(&game::INITIAL_STATE)?,
+    };
+    if let Ok(path) = std::env::var("SAVE_SOLUTION") {
+        solution
+            .save(&path)
+            .with_context(|| format!("failed to save {path}"))?;
+    }
+    // e.g. to load the solved tables into a notebook or a spreadsheet
+    if let Ok(path) = std::env::var("SAVE_CSV") {
+        solution
+            .save_csv(&path)
+            .with_context(|| format!("failed to save {path}"))?;
+    }
+    if let Ok(path) = std::env::var("SAVE_JSON") {
+        solution
+            .save_json(&path)
+            .with_context(|| format!("failed to save {path}"))?;
+    }
+    // e.g. to visualise why the initial state is a win or a draw, via `dot -Tsvg`
+    if let Ok(path) = std::env::var("SAVE_DOT") {
+        let optimal_only = std::env::var("DOT_OPTIMAL_ONLY").is_ok_and(|val| val == "1");
+        let state_graph = solution.state_graph(&game::INITIAL_STATE, optimal_only);
+        std::fs::write(&path, format!("{state_graph}\n"))
+            .with_context(|| format!("failed to save {path}"))?;
+    }
+
+    // e.g. to see how close a non-exhaustive search gets to the exact policy
+    if std::env::var("COMPARE_MCTS").is_ok_and(|val| val == "1") {
+        solution.ensure_exhaustive()?;
+        let mut mcts = solver::Mcts::new(*solver::MCTS_BUDGET, *solver::MCTS_SEED);
+        println!();
+        println!(
+            "mcts ({} per move, seed {}):",
+            mcts.budget,
+            *solver::MCTS_SEED
+        );
+        println!();
+        println!("{}", mcts.compare(&solution, &game::INITIAL_STATE)?);
+    }
+
+    // e.g. to see how well the learned greedy policy plays
+    if std::env::var("COMPARE_QLEARNING").is_ok_and(|val| val == "1") {
+        solution.ensure_exhaustive()?;
+        let qlearning = solver::QLearning::new_trained(*solver::QLEARNING, &game::INITIAL_STATE);
+        println!();
+        println!("q-learning ({}):", qlearning.hyperparameters);
+        println!();
+        println!(
+            "{}",
+            solution.compare(&game::INITIAL_STATE, |s| qlearning.greedy_action(s))?
+        );
+    }
+
+    // e.g. to see the line of play behind the value of the initial state
+    if std::env::var("PRINCIPAL_VARIATION").is_ok_and(|val| val == "1") {
+        print_principal_variation(solution.principal_variation(&game::INITIAL_STATE));
+    }
+
+    println!();
     println!("result:");
-    println!("");
-    println!("{p1_agent}");
-    println!("");
-    println!("{p2_agent}");
+    println!();
+    println!("{}", solution.p1_agent);
+    println!();
+    println!("{}", solution.p2_agent);
+    Ok(())
+}
+
+/// the same line as the initial state in the solved tables, e.g. `_,_,_,_ [WIN(3)] -> {(1), (2)}`
+///
+/// note: the initial state is printed as is rather than canonicalised
+fn print_initial_state_solved_by_gaps() -> anyhow::Result<()> {
+    anyhow::ensure!(
+        std::env::var("LOAD_SOLUTION").is_err()
+            && std::env::var("SAVE_SOLUTION").is_err()
+            && std::env::var("SAVE_CSV").is_err()
+            && std::env::var("SAVE_JSON").is_err()
+            && std::env::var("SAVE_DOT").is_err(),
+        "the gap solver has no tables to load or save"
+    );
+    let mut gap_solver = solver::GapSolver::new()?;
+    let state = &*game::INITIAL_STATE;
+
+    let best_moves = gap_solver
+        .best_moves(state)
+        .iter()
+        .map(|act| act.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    println!();
+    println!("result:");
+    println!();
+    println!(
+        "{} [{}] -> {{{best_moves}}}",
+        solver::Position(state),
+        gap_solver.value(state)
+    );
+
+    if std::env::var("PRINCIPAL_VARIATION").is_ok_and(|val| val == "1") {
+        print_principal_variation(gap_solver.principal_variation(state));
+    }
+    Ok(())
+}
+
+fn print_principal_variation(principal_variation: Option<solver::PrincipalVariation>) {
+    println!();
+    println!("principal variation:");
+    println!();
+    match principal_variation {
+        Some(principal_variation) => println!("{principal_variation}"),
+        // e.g. the game is already over at the initial state
+        None => println!("none"),
+    }
 }
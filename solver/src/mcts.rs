@@ -0,0 +1,239 @@
+use once_cell::sync::Lazy;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// how long `Mcts` searches for each move
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+    Copy,
+    Debug,
+    // hashable:
+    PartialEq,
+    Eq,
+    Hash,
+)]
+pub enum Budget {
+    Iterations(u32),
+    Time(std::time::Duration),
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Budget::Iterations(1000)
+    }
+}
+
+impl std::fmt::Display for Budget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Budget::Iterations(iterations) => write!(f, "{iterations} iterations"),
+            Budget::Time(duration) => write!(f, "{duration:?}"),
+        }
+    }
+}
+
+/// configurable via env `MCTS_ITERATIONS` or `MCTS_TIME_MS` (the time per move in milliseconds)
+pub static MCTS_BUDGET: Lazy<Budget> = Lazy::new(|| {
+    (|| -> anyhow::Result<Option<Budget>> {
+        if let Ok(val) = std::env::var("MCTS_TIME_MS") {
+            let millis = val.parse::<u64>()?;
+            anyhow::ensure!(millis > 0, "MCTS_TIME_MS should be positive");
+            return Ok(Some(Budget::Time(std::time::Duration::from_millis(millis))));
+        }
+        if let Ok(val) = std::env::var("MCTS_ITERATIONS") {
+            let iterations = val.parse::<u32>()?;
+            anyhow::ensure!(iterations > 0, "MCTS_ITERATIONS should be positive");
+            return Ok(Some(Budget::Iterations(iterations)));
+        }
+        Ok(None)
+    })()
+    .unwrap_or_else(|err| panic!("invalid MCTS budget: {err}"))
+    .unwrap_or_default()
+});
+
+/// configurable via env `MCTS_SEED`
+pub static MCTS_SEED: Lazy<u64> = Lazy::new(|| match std::env::var("MCTS_SEED") {
+    Ok(val) => val
+        .parse::<u64>()
+        .unwrap_or_else(|err| panic!("invalid MCTS_SEED `{val}`: {err}")),
+    Err(_) => 0,
+});
+
+struct Node {
+    state: game::State,
+    result: game::Result, // of the action leading to this node
+    mover: game::Player,  // who took the action leading to this node
+    action: Option<game::Action>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    untried_actions: Vec<game::Action>, // shuffled
+
+    visits: u32,
+    total_reward: f64, // for `mover`, i.e. 1 for a win, 0.5 for a draw and 0 for a loss
+}
+
+/// Monte Carlo tree search with UCT selection and uniformly random playouts
+///
+/// note: unlike `crate::Agent`, nothing is stored beyond the search of a single move
+pub struct Mcts {
+    pub budget: Budget,
+    pub exploration: f64, // the UCT constant, `sqrt(2)` by default
+    rng: rand::rngs::StdRng,
+}
+
+impl Mcts {
+    /// the same seed (and iteration budget) always searches the same
+    pub fn new(budget: Budget, seed: u64) -> Mcts {
+        Mcts {
+            budget,
+            exploration: std::f64::consts::SQRT_2,
+            rng: rand::rngs::StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// the most visited action at `state`, or `None` if there is no legal action
+    pub fn best_action(&mut self, state: &game::State) -> Option<game::Action> {
+        self.search(state)
+            .into_iter()
+            .max_by_key(|(_, visits, _)| *visits)
+            .map(|(act, _, _)| act)
+    }
+
+    /// the number of visits and the mean reward (from 0 for a loss to 1 for a win) of every legal action at `state`,
+    /// sorted by action
+    pub fn search(&mut self, state: &game::State) -> Vec<(game::Action, u32, f64)> {
+        let mut tree = vec![self.new_node(
+            state.clone(),
+            game::Result::WaitingNextAction,
+            state.player_to_act.next(),
+            None,
+            None,
+        )];
+        let started_at = std::time::Instant::now();
+        let mut iterations = 0;
+        while match self.budget {
+            Budget::Iterations(max) => iterations < max,
+            Budget::Time(max) => iterations == 0 || started_at.elapsed() < max,
+        } {
+            iterations += 1;
+            let leaf = self.select_and_expand(&mut tree);
+            let winner = self.playout(&tree[leaf]);
+            Mcts::backpropagate(&mut tree, leaf, winner);
+        }
+
+        let mut v = tree[0]
+            .children
+            .iter()
+            .map(|&i| {
+                let node = &tree[i];
+                (
+                    node.action.unwrap(),
+                    node.visits,
+                    node.total_reward / node.visits.max(1) as f64,
+                )
+            })
+            .collect::<Vec<_>>();
+        v.sort_unstable_by_key(|(act, _, _)| *act);
+        v
+    }
+
+    fn new_node(
+        &mut self,
+        state: game::State,
+        result: game::Result,
+        mover: game::Player,
+        action: Option<game::Action>,
+        parent: Option<usize>,
+    ) -> Node {
+        let mut untried_actions = match result {
+            game::Result::WaitingNextAction => state.all_actions(),
+            game::Result::Draw | game::Result::Win => vec![],
+        };
+        untried_actions.shuffle(&mut self.rng);
+        Node {
+            state,
+            result,
+            mover,
+            action,
+            parent,
+            children: vec![],
+            untried_actions,
+            visits: 0,
+            total_reward: 0.0,
+        }
+    }
+
+    /// descend by UCT until a node with untried actions, then expand one of them
+    fn select_and_expand(&mut self, tree: &mut Vec<Node>) -> usize {
+        let mut i = 0;
+        loop {
+            if let Some(act) = tree[i].untried_actions.pop() {
+                let (state, result) = tree[i].state.step(&act);
+                let mover = tree[i].state.player_to_act;
+                let child = self.new_node(state, result, mover, Some(act), Some(i));
+                tree.push(child);
+                let child = tree.len() - 1;
+                tree[i].children.push(child);
+                return child;
+            }
+            if tree[i].children.is_empty() {
+                // the game is over
+                return i;
+            }
+            let log_visits = (tree[i].visits as f64).ln();
+            i = *tree[i]
+                .children
+                .iter()
+                .max_by(|&&a, &&b| {
+                    let uct = |node: &Node| {
+                        node.total_reward / node.visits as f64
+                            + self.exploration * (log_visits / node.visits as f64).sqrt()
+                    };
+                    uct(&tree[a]).total_cmp(&uct(&tree[b]))
+                })
+                .unwrap();
+        }
+    }
+
+    /// play uniformly random actions until the game is over, returning the winner (if any)
+    fn playout(&mut self, node: &Node) -> Option<game::Player> {
+        let mut state = node.state.clone();
+        let mut result = node.result;
+        let mut mover = node.mover;
+        while matches!(result, game::Result::WaitingNextAction) {
+            let act = *state.all_actions().choose(&mut self.rng).unwrap();
+            mover = state.player_to_act;
+            (state, result) = state.step(&act);
+        }
+        matches!(result, game::Result::Win).then_some(mover)
+    }
+
+    fn backpropagate(tree: &mut [Node], leaf: usize, winner: Option<game::Player>) {
+        let mut i = Some(leaf);
+        while let Some(node) = i.map(|i| &mut tree[i]) {
+            node.visits += 1;
+            node.total_reward += match winner {
+                Some(player) if player == node.mover => 1.0,
+                Some(_) => 0.0,
+                None => 0.5,
+            };
+            i = node.parent;
+        }
+    }
+}
+
+/// how often MCTS picks an action as good as the solved one, see `Mcts::compare`
+pub type MctsReport = crate::Comparison;
+
+impl Mcts {
+    /// search every state reachable from `initial_state` (up to symmetry) and compare the picked actions
+    /// with the action values of `solution`, see `crate::Solution::compare`
+    pub fn compare(
+        &mut self,
+        solution: &crate::Solution,
+        initial_state: &game::State,
+    ) -> anyhow::Result<MctsReport> {
+        solution.compare(initial_state, |s| self.best_action(s))
+    }
+}
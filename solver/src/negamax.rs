@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+#[derive(
+    // sane defaults for unit-like enums:
+    Clone,
+    Copy,
+    Debug,
+)]
+pub(crate) enum Bound {
+    Exact,
+    Lower, // the search failed high, i.e. the exact value is at least this value
+    Upper, // the search failed low, i.e. the exact value is at most this value
+}
+
+struct Entry {
+    value: crate::Value,
+    bound: Bound,
+    // OPTIMIZATION: searched first when the state is visited again
+    // note: in the orientation of the canonical state
+    best_action: Option<game::Action>,
+}
+
+/// negamax search with alpha-beta pruning, move ordering and a transposition table
+///
+/// values are always from the perspective of the player to act
+#[derive(Default)]
+pub struct Negamax {
+    transposition_table: HashMap<crate::EncodedState, Entry>,
+    // positions this many plies ahead count as a draw, see `Negamax::new_depth_limited`
+    max_plies: Option<u32>,
+}
+
+impl Negamax {
+    pub fn new() -> Negamax {
+        Negamax::default()
+    }
+
+    /// a search cut off after `max_plies` plies, e.g. a weaker opponent in a tournament, see `crate::DepthLimited`
+    ///
+    /// note: the values are only exact within the horizon, so nothing is memoised
+    pub fn new_depth_limited(max_plies: u32) -> Negamax {
+        assert!(max_plies > 0, "the search should look at least 1 ply ahead");
+        Negamax {
+            max_plies: Some(max_plies),
+            ..Negamax::default()
+        }
+    }
+
+    /// the exact value of `state` (or as seen within the horizon)
+    pub fn value(&mut self, state: &game::State) -> crate::Value {
+        self.search(state, 0, crate::Value::MIN, crate::Value::MAX)
+    }
+
+    /// the exact value of taking `action` at `state` (or as seen within the horizon)
+    pub fn action_value(&mut self, state: &game::State, action: &game::Action) -> crate::Value {
+        self.search_action(state, action, 0, crate::Value::MIN, crate::Value::MAX)
+    }
+
+    /// `plies` is the number of plies searched before `state`
+    fn search(
+        &mut self,
+        state: &game::State,
+        plies: u32,
+        mut alpha: crate::Value,
+        mut beta: crate::Value,
+    ) -> crate::Value {
+        if let Some(max_plies) = self.max_plies {
+            if plies >= max_plies {
+                return crate::Value::DRAW;
+            }
+        }
+
+        // OPTIMIZATION: symmetric states share the same entry
+        let (encoded_state, symmetry) = crate::EncodedState::new(state).canonical();
+        let orig_alpha = alpha;
+
+        let mut hint = None;
+        if let Some(entry) = self.transposition_table.get(&encoded_state) {
+            match entry.bound {
+                Bound::Exact => return entry.value,
+                Bound::Lower => alpha = alpha.max(entry.value),
+                Bound::Upper => beta = beta.min(entry.value),
+            }
+            if alpha >= beta {
+                return entry.value;
+            }
+            hint = entry.best_action.map(|act| symmetry.inverse().action(&act));
+        }
+
+        let mut best_value = crate::Value::new();
+        let mut best_action = None;
+        for act in Negamax::ordered_actions(state, hint) {
+            let value = self.search_action(state, &act, plies, alpha, beta);
+            if value > best_value {
+                best_value = value;
+                best_action = Some(act);
+            }
+            alpha = alpha.max(value);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        if self.max_plies.is_none() {
+            self.transposition_table.insert(
+                encoded_state,
+                Entry {
+                    value: best_value,
+                    bound: if best_value <= orig_alpha {
+                        Bound::Upper
+                    } else if best_value >= beta {
+                        Bound::Lower
+                    } else {
+                        Bound::Exact
+                    },
+                    best_action: best_action.map(|act| symmetry.action(&act)),
+                },
+            );
+        }
+        best_value
+    }
+
+    fn search_action(
+        &mut self,
+        state: &game::State,
+        action: &game::Action,
+        plies: u32,
+        alpha: crate::Value,
+        beta: crate::Value,
+    ) -> crate::Value {
+        let (s_next, result) = state.step(action);
+        match result {
+            game::Result::Win => crate::Value::WIN(1),
+            game::Result::Draw => crate::Value::DRAW,
+            game::Result::WaitingNextAction => {
+                let is_same_player = s_next.player_to_act == state.player_to_act;
+                let (alpha, beta) = crate::Value::window_through(alpha, beta, is_same_player);
+                self.search(&s_next, plies + 1, alpha, beta)
+                    .through(is_same_player)
+            }
+        }
+    }
+
+    /// the best action found before (if any) goes first, then actions winning immediately,
+    /// then actions next to the opponent's stones (likely blocking), and passing goes last
+    fn ordered_actions(state: &game::State, hint: Option<game::Action>) -> Vec<game::Action> {
+        let mut actions = state.all_actions();
+        actions.sort_by_cached_key(|act| {
+            if Some(*act) == hint {
+                return 0;
+            }
+            match *act {
+                game::Action::Place { row, col } => {
+                    let neighbours = &game::BOARD.neighbours[game::BOARD.cell(row, col)];
+                    if neighbours
+                        .iter()
+                        .any(|&i| state.board[i] == Some(state.player_to_act))
+                    {
+                        1
+                    } else if neighbours.iter().any(|&i| state.board[i].is_some()) {
+                        2
+                    } else {
+                        3
+                    }
+                }
+                game::Action::Pass => 4,
+            }
+        });
+        actions
+    }
+}
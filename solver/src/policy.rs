@@ -0,0 +1,44 @@
+// note: ties between equally good actions are broken by the order of `game::Action`
+
+/// perfect play from the solved tables
+///
+/// ASSUMPTION: the state is reachable from the solved initial state, see `crate::Solution::best_moves`
+impl game::Policy for crate::Solution {
+    fn choose(&mut self, state: &game::State) -> game::Action {
+        self.best_moves(state)
+            .and_then(|acts| acts.first().copied())
+            .expect("the state should be reachable from the solved initial state")
+    }
+}
+
+/// perfect play by searching every action
+impl game::Policy for crate::Negamax {
+    fn choose(&mut self, state: &game::State) -> game::Action {
+        let mut action_values = state
+            .all_actions()
+            .into_iter()
+            .map(|act| (act, self.action_value(state, &act)))
+            .collect::<Vec<_>>();
+        action_values.sort_unstable_by_key(|(act, _)| *act);
+        let max_value = action_values.iter().map(|(_, value)| *value).max();
+        action_values
+            .into_iter()
+            .find(|(_, value)| Some(*value) == max_value)
+            .map(|(act, _)| act)
+            .unwrap()
+    }
+}
+
+/// perfect play on a single-row board, see `crate::GapSolver::new`
+impl game::Policy for crate::GapSolver {
+    fn choose(&mut self, state: &game::State) -> game::Action {
+        self.best_moves(state)[0]
+    }
+}
+
+/// the most visited action
+impl game::Policy for crate::Mcts {
+    fn choose(&mut self, state: &game::State) -> game::Action {
+        self.best_action(state).unwrap()
+    }
+}
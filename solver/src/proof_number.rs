@@ -0,0 +1,365 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+// note: proof and disproof numbers saturate at infinity
+const INFINITY: u64 = u64::MAX;
+
+/// the number of an OR node from the numbers of its children (the proof number) or of an AND node (the disproof number)
+fn min(numbers: impl Iterator<Item = u64>) -> u64 {
+    numbers.min().unwrap_or(INFINITY)
+}
+
+/// the other number of an OR node or an AND node, see `min`
+fn sum(numbers: impl Iterator<Item = u64>) -> u64 {
+    numbers.fold(0, |acc, n| acc.saturating_add(n))
+}
+
+#[derive(
+    // sane defaults for unit-like enums:
+    Clone,
+    Copy,
+    derive_more::Display,
+    Debug,
+    // hashable:
+    PartialEq,
+    Eq,
+    Hash,
+)]
+pub enum ProofSearch {
+    Pns,  // see `crate::PnSearch`
+    DfPn, // see `crate::DfPn`
+}
+
+/// `pns` or `dfpn`
+impl std::str::FromStr for ProofSearch {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<ProofSearch> {
+        match s {
+            "pns" => Ok(ProofSearch::Pns),
+            "dfpn" => Ok(ProofSearch::DfPn),
+            _ => anyhow::bail!("unknown proof search `{s}`, expected `pns` or `dfpn`"),
+        }
+    }
+}
+
+/// configurable via env `PROOF_SEARCH` (none by default)
+pub static PROOF_SEARCH: Lazy<Option<ProofSearch>> = Lazy::new(|| {
+    std::env::var("PROOF_SEARCH").ok().map(|val| {
+        val.parse::<ProofSearch>()
+            .unwrap_or_else(|err| panic!("{err}"))
+    })
+});
+
+/// the maximum number of nodes to search per proof, configurable via env `PROOF_MAX_NODES` (unlimited by default)
+pub static PROOF_MAX_NODES: Lazy<Option<u64>> = Lazy::new(|| {
+    std::env::var("PROOF_MAX_NODES").ok().map(|val| {
+        (|| -> anyhow::Result<u64> {
+            let max_nodes = val.parse::<u64>()?;
+            anyhow::ensure!(max_nodes > 0, "there should be at least 1 node");
+            Ok(max_nodes)
+        })()
+        .unwrap_or_else(|err| panic!("invalid PROOF_MAX_NODES `{val}`: {err}"))
+    })
+});
+
+/// whether the player to act can force a win, see `PnSearch` and `DfPn`
+#[derive(
+    // sane defaults for unit-like enums:
+    Clone,
+    Copy,
+    derive_more::Display,
+    Debug,
+    // hashable:
+    PartialEq,
+    Eq,
+    Hash,
+)]
+pub enum Proof {
+    #[display(fmt = "WIN")]
+    Win, // the player to act can force a win
+    #[display(fmt = "NO WIN")]
+    NoWin, // the opponent can force a draw or a win
+    #[display(fmt = "UNKNOWN")]
+    Unknown, // the node budget runs out first
+}
+
+struct PnNode {
+    state: game::State,
+    is_or: bool, // whether the player proving a win acts, otherwise the opponent acts
+    parent: Option<usize>,
+    children: Vec<usize>,
+    is_expanded: bool,
+    proof: u64,
+    disproof: u64,
+}
+
+/// best-first proof-number search, where the whole search tree is kept in memory
+///
+/// note: unlike `crate::Negamax`, only a win or not is proven, so much less of the tree needs to be searched
+pub struct PnSearch {
+    pub max_nodes: Option<u64>,
+    nodes: u64,
+}
+
+impl PnSearch {
+    /// `max_nodes` (if any) limits the number of expanded nodes per proof
+    pub fn new(max_nodes: Option<u64>) -> PnSearch {
+        PnSearch {
+            max_nodes,
+            nodes: 0,
+        }
+    }
+
+    /// the number of nodes expanded in the last proof
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// whether the player to act at `state` can force a win
+    ///
+    /// ASSUMPTION: the game is not over at `state`, i.e. there is any legal action
+    pub fn prove(&mut self, state: &game::State) -> Proof {
+        self.nodes = 0;
+        let prover = state.player_to_act;
+        let mut tree = vec![PnNode {
+            state: state.clone(),
+            is_or: true,
+            parent: None,
+            children: vec![],
+            is_expanded: false,
+            proof: 1,
+            disproof: 1,
+        }];
+        while tree[0].proof != 0 && tree[0].disproof != 0 {
+            if self
+                .max_nodes
+                .is_some_and(|max_nodes| self.nodes >= max_nodes)
+            {
+                return Proof::Unknown;
+            }
+            self.nodes += 1;
+
+            // the most-proving node
+            let mut i = 0;
+            while tree[i].is_expanded {
+                let node = &tree[i];
+                i = if node.is_or {
+                    *node
+                        .children
+                        .iter()
+                        .min_by_key(|&&c| tree[c].proof)
+                        .unwrap()
+                } else {
+                    *node
+                        .children
+                        .iter()
+                        .min_by_key(|&&c| tree[c].disproof)
+                        .unwrap()
+                };
+            }
+
+            PnSearch::expand(&mut tree, i, prover);
+            let mut next = Some(i);
+            while let Some(i) = next {
+                let children = tree[i].children.iter().map(|&c| &tree[c]);
+                let proofs = children.clone().map(|c| c.proof);
+                let disproofs = children.map(|c| c.disproof);
+                let (proof, disproof) = if tree[i].is_or {
+                    (min(proofs), sum(disproofs))
+                } else {
+                    (sum(proofs), min(disproofs))
+                };
+                tree[i].proof = proof;
+                tree[i].disproof = disproof;
+                next = tree[i].parent;
+            }
+        }
+        if tree[0].proof == 0 {
+            Proof::Win
+        } else {
+            Proof::NoWin
+        }
+    }
+
+    fn expand(tree: &mut Vec<PnNode>, i: usize, prover: game::Player) {
+        let state = tree[i].state.clone();
+        for act in state.all_actions() {
+            let (s_next, result) = state.step(&act);
+            let (proof, disproof, is_expanded) = match result {
+                game::Result::Win if state.player_to_act == prover => (0, INFINITY, true),
+                game::Result::Win | game::Result::Draw => (INFINITY, 0, true),
+                // OPTIMIZATION: initialised by mobility, see `DfPn::numbers`
+                game::Result::WaitingNextAction if s_next.player_to_act == prover => {
+                    (1, s_next.all_actions().len() as u64, false)
+                }
+                game::Result::WaitingNextAction => (s_next.all_actions().len() as u64, 1, false),
+            };
+            tree.push(PnNode {
+                is_or: s_next.player_to_act == prover,
+                state: s_next,
+                parent: Some(i),
+                children: vec![],
+                is_expanded,
+                proof,
+                disproof,
+            });
+            let child = tree.len() - 1;
+            tree[i].children.push(child);
+        }
+        tree[i].is_expanded = true;
+    }
+}
+
+/// depth-first proof-number search (df-pn) with a transposition table, which needs much less memory than `PnSearch`
+///
+/// note: the numbers are of a win for the player to act at the searched state (the prover), rather than for whoever
+/// acts at each state, since a draw disproves a win for both players
+#[derive(Default)]
+pub struct DfPn {
+    pub max_nodes: Option<u64>,
+    nodes: u64,
+    prover: game::Player,
+    // note: keyed by states as is rather than `crate::EncodedState`, so that any board size can be searched
+    transposition_table: HashMap<game::State, (u64, u64)>, // of the current prover
+}
+
+impl DfPn {
+    /// `max_nodes` (if any) limits the number of visited nodes per proof
+    pub fn new(max_nodes: Option<u64>) -> DfPn {
+        DfPn {
+            max_nodes,
+            ..DfPn::default()
+        }
+    }
+
+    /// the number of nodes visited in the last proof
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// whether the player to act at `state` can force a win
+    ///
+    /// ASSUMPTION: the game is not over at `state`, i.e. there is any legal action
+    pub fn prove(&mut self, state: &game::State) -> Proof {
+        self.nodes = 0;
+        if self.prover != state.player_to_act {
+            self.prover = state.player_to_act;
+            self.transposition_table.clear();
+        }
+        match self.search(state, INFINITY - 1, INFINITY - 1) {
+            (0, _) => Proof::Win,
+            (_, 0) => Proof::NoWin,
+            _ => Proof::Unknown,
+        }
+    }
+
+    /// an action proving a win for the player to act at `state`, or `None` if there is no proof
+    pub fn winning_action(&mut self, state: &game::State) -> Option<game::Action> {
+        if self.prove(state) != Proof::Win {
+            return None;
+        }
+        state.all_actions().into_iter().find(|act| {
+            let (s_next, result) = state.step(act);
+            self.numbers(state, &s_next, &result).0 == 0
+        })
+    }
+
+    /// the proof and disproof numbers of `state` once either reaches its threshold
+    fn search(&mut self, state: &game::State, max_proof: u64, max_disproof: u64) -> (u64, u64) {
+        let is_or = state.player_to_act == self.prover;
+        // OPTIMIZATION: stepped only once, since only the numbers of the children change between iterations
+        let actions = state.all_actions();
+        let next_states = actions
+            .iter()
+            .map(|act| state.step(act))
+            .collect::<Vec<_>>();
+        // OPTIMIZATION: ties are broken towards cells with more empty neighbours, i.e. more ways to connect
+        let empty_neighbours = actions
+            .iter()
+            .map(|act| match *act {
+                game::Action::Place { row, col } => game::BOARD.neighbours
+                    [game::BOARD.cell(row, col)]
+                .iter()
+                .filter(|&&i| state.board[i].is_none())
+                .count(),
+                game::Action::Pass => 0,
+            })
+            .collect::<Vec<_>>();
+        loop {
+            let children = next_states
+                .iter()
+                .map(|(s_next, result)| self.numbers(state, s_next, result))
+                .collect::<Vec<_>>();
+            let proofs = children.iter().map(|(proof, _)| *proof);
+            let disproofs = children.iter().map(|(_, disproof)| *disproof);
+            let (proof, disproof) = if is_or {
+                (min(proofs), sum(disproofs))
+            } else {
+                (sum(proofs), min(disproofs))
+            };
+            self.transposition_table
+                .insert(state.clone(), (proof, disproof));
+            if proof >= max_proof || disproof >= max_disproof {
+                return (proof, disproof);
+            }
+            if self
+                .max_nodes
+                .is_some_and(|max_nodes| self.nodes >= max_nodes)
+            {
+                return (proof, disproof);
+            }
+            self.nodes += 1;
+
+            // the most-proving child, and the threshold to switch to the second best one
+            let key = |c: &(u64, u64)| if is_or { c.0 } else { c.1 };
+            let (best, best_child) = children
+                .iter()
+                .enumerate()
+                .min_by_key(|(i, c)| (key(c), std::cmp::Reverse(empty_neighbours[*i])))
+                .unwrap();
+            let second_best = children
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != best)
+                .map(|(_, c)| key(c))
+                .min()
+                .unwrap_or(INFINITY);
+            let (max_proof, max_disproof) = if is_or {
+                (
+                    max_proof.min(second_best.saturating_add(1)),
+                    (max_disproof - disproof).saturating_add(best_child.1),
+                )
+            } else {
+                (
+                    (max_proof - proof).saturating_add(best_child.0),
+                    max_disproof.min(second_best.saturating_add(1)),
+                )
+            };
+
+            self.search(&next_states[best].0, max_proof, max_disproof);
+        }
+    }
+
+    /// the proof and disproof numbers of stepping from `state` to `s_next`
+    fn numbers(
+        &self,
+        state: &game::State,
+        s_next: &game::State,
+        result: &game::Result,
+    ) -> (u64, u64) {
+        match result {
+            game::Result::Win if state.player_to_act == self.prover => (0, INFINITY),
+            game::Result::Win | game::Result::Draw => (INFINITY, 0),
+            // OPTIMIZATION: an unknown state is initialised by its mobility, i.e. it takes more to prove (or disprove)
+            // that every action of the opponent (or the prover) fails
+            game::Result::WaitingNextAction => match self.transposition_table.get(s_next) {
+                Some(&numbers) => numbers,
+                None if s_next.player_to_act == self.prover => {
+                    (1, s_next.all_actions().len() as u64)
+                }
+                None => (s_next.all_actions().len() as u64, 1),
+            },
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use once_cell::sync::Lazy;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+
+/// tunables of `QLearning`
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+    Copy,
+    Debug,
+)]
+pub struct Hyperparameters {
+    pub episodes: u32,
+    pub learning_rate: f64, // alpha
+    pub discount: f64,      // gamma, where `< 1` prefers faster wins and slower losses
+    pub epsilon: f64,       // the probability of exploring a random action
+    pub seed: u64,
+}
+
+impl Default for Hyperparameters {
+    fn default() -> Self {
+        Hyperparameters {
+            episodes: 20000,
+            learning_rate: 0.1,
+            discount: 0.9,
+            epsilon: 0.2,
+            seed: 0,
+        }
+    }
+}
+
+/// configurable via env `QLEARNING_EPISODES`, `QLEARNING_RATE`, `QLEARNING_DISCOUNT`, `QLEARNING_EPSILON`
+/// and `QLEARNING_SEED`
+pub static QLEARNING: Lazy<Hyperparameters> = Lazy::new(|| {
+    fn var<T: std::str::FromStr>(key: &str, default: T) -> T
+    where
+        T::Err: std::fmt::Display,
+    {
+        match std::env::var(key) {
+            Ok(val) => val
+                .parse::<T>()
+                .unwrap_or_else(|err| panic!("invalid {key} `{val}`: {err}")),
+            Err(_) => default,
+        }
+    }
+    let default = Hyperparameters::default();
+    let hyperparameters = Hyperparameters {
+        episodes: var("QLEARNING_EPISODES", default.episodes),
+        learning_rate: var("QLEARNING_RATE", default.learning_rate),
+        discount: var("QLEARNING_DISCOUNT", default.discount),
+        epsilon: var("QLEARNING_EPSILON", default.epsilon),
+        seed: var("QLEARNING_SEED", default.seed),
+    };
+    assert!(
+        (0.0..=1.0).contains(&hyperparameters.epsilon),
+        "invalid QLEARNING_EPSILON `{}`: it should be a probability",
+        hyperparameters.epsilon
+    );
+    hyperparameters
+});
+
+impl std::fmt::Display for Hyperparameters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} episodes, learning rate {}, discount {}, epsilon {}, seed {}",
+            self.episodes, self.learning_rate, self.discount, self.epsilon, self.seed
+        )
+    }
+}
+
+/// tabular Q-learning by epsilon-greedy self-play
+///
+/// unlike `crate::Agent`, both players share one table, where a Q-value is from the perspective of the player to act,
+/// i.e. 1 for a win, 0 for a draw and -1 for a loss (discounted by the plies until the result)
+pub struct QLearning {
+    pub hyperparameters: Hyperparameters,
+    // note: keyed by canonical states like `crate::Agent`
+    q_values: HashMap<crate::EncodedState, HashMap<game::Action, f64>>,
+    rng: rand::rngs::StdRng,
+}
+
+impl QLearning {
+    pub fn new(hyperparameters: Hyperparameters) -> QLearning {
+        QLearning {
+            hyperparameters,
+            q_values: HashMap::new(),
+            rng: rand::rngs::StdRng::seed_from_u64(hyperparameters.seed),
+        }
+    }
+
+    /// train for `hyperparameters.episodes` games from `initial_state`, e.g. `game::INITIAL_STATE`
+    pub fn new_trained(hyperparameters: Hyperparameters, initial_state: &game::State) -> QLearning {
+        let mut qlearning = QLearning::new(hyperparameters);
+        for episode in 1..=hyperparameters.episodes {
+            qlearning.play_episode(initial_state);
+            if episode % 1000 == 0 {
+                log::debug!(
+                    target: "solver::qlearning",
+                    episode = episode,
+                    states = qlearning.q_values.len();
+                    "trained"
+                );
+            }
+        }
+        log::info!(
+            target: "solver::qlearning",
+            episodes = hyperparameters.episodes,
+            states = qlearning.q_values.len();
+            "trained"
+        );
+        qlearning
+    }
+
+    /// the learned Q-value of taking `action` at `state`, `0` if never updated
+    pub fn q_value(&self, state: &game::State, action: &game::Action) -> f64 {
+        let (canonical, symmetry) = crate::EncodedState::new(state).canonical();
+        self.q_values
+            .get(&canonical)
+            .and_then(|m| m.get(&symmetry.action(action)))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// the action with the highest Q-value at `state`, or `None` if `state` is never seen in training
+    ///
+    /// note: ties are broken by the order of `game::State::all_actions`
+    pub fn greedy_action(&self, state: &game::State) -> Option<game::Action> {
+        let (canonical, _) = crate::EncodedState::new(state).canonical();
+        if !self.q_values.contains_key(&canonical) {
+            return None;
+        }
+        self.max_action(state)
+    }
+
+    fn max_action(&self, state: &game::State) -> Option<game::Action> {
+        state
+            .all_actions()
+            .into_iter()
+            .map(|act| (act, self.q_value(state, &act)))
+            // note: `max_by` keeps the last maximum, hence reversed
+            .rev()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(act, _)| act)
+    }
+
+    fn max_q_value(&self, state: &game::State) -> f64 {
+        self.max_action(state)
+            .map(|act| self.q_value(state, &act))
+            .unwrap_or_default()
+    }
+
+    fn play_episode(&mut self, initial_state: &game::State) {
+        let Hyperparameters {
+            learning_rate,
+            discount,
+            epsilon,
+            ..
+        } = self.hyperparameters;
+
+        let mut s = initial_state.clone();
+        loop {
+            let act = if self.rng.gen_bool(epsilon) {
+                *s.all_actions().choose(&mut self.rng).unwrap()
+            } else {
+                self.max_action(&s).unwrap()
+            };
+            let (s_next, result) = s.step(&act);
+            let target = match result {
+                game::Result::Win => 1.0,
+                game::Result::Draw => 0.0,
+                // note: like `crate::Value::through`, but discounted
+                game::Result::WaitingNextAction if s_next.player_to_act == s.player_to_act => {
+                    discount * self.max_q_value(&s_next)
+                }
+                game::Result::WaitingNextAction => -discount * self.max_q_value(&s_next),
+            };
+
+            let (canonical, symmetry) = crate::EncodedState::new(&s).canonical();
+            let q_value = self
+                .q_values
+                .entry(canonical)
+                .or_default()
+                .entry(symmetry.action(&act))
+                .or_default();
+            *q_value += learning_rate * (target - *q_value);
+
+            if !matches!(result, game::Result::WaitingNextAction) {
+                break;
+            }
+            s = s_next;
+        }
+    }
+}
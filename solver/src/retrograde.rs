@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+enum Edge {
+    Terminal(crate::Value), // the value of the action, which ends the game
+    To {
+        node: usize,
+        is_same_player: bool, // see `crate::Value::through`
+    },
+}
+
+struct Node {
+    edges: Vec<Edge>,
+    predecessors: Vec<usize>, // once per edge into this node
+    unresolved: usize,        // the number of edges to nodes without a value yet
+    value: crate::Value,
+}
+
+/// retrograde analysis, i.e. the exact value of every position reachable from some position,
+/// solved backwards from the end of the game, e.g. an endgame table when starting from a late position
+///
+/// values are always from the perspective of the player to act, like `crate::Negamax`
+pub struct Retrograde {
+    // note: keyed by canonical states like `crate::Agent`
+    values: HashMap<crate::EncodedState, crate::Value>,
+}
+
+impl Retrograde {
+    /// enumerate every position reachable from `initial_state` (e.g. `game::INITIAL_STATE`), then propagate the values
+    /// of the actions ending the game backwards, where a position is solved once all its successors are
+    ///
+    /// ASSUMPTION: there are no loops in states, i.e. the state-space is a one-way tree without loops
+    pub fn new(initial_state: &game::State) -> Retrograde {
+        let started_at = std::time::Instant::now();
+        let (keys, mut nodes) = Retrograde::enumerate(initial_state);
+        log::info!(
+            target: "solver::retrograde",
+            positions = nodes.len(),
+            elapsed:? = started_at.elapsed();
+            "enumerated"
+        );
+
+        // seeded by the positions where every action ends the game
+        let mut resolved = (0..nodes.len())
+            .filter(|&i| nodes[i].unresolved == 0)
+            .collect::<Vec<_>>();
+        while let Some(i) = resolved.pop() {
+            let value = nodes[i]
+                .edges
+                .iter()
+                .map(|edge| match *edge {
+                    Edge::Terminal(value) => value,
+                    Edge::To {
+                        node,
+                        is_same_player,
+                    } => nodes[node].value.through(is_same_player),
+                })
+                .max()
+                .unwrap_or_default();
+            nodes[i].value = value;
+            for j in std::mem::take(&mut nodes[i].predecessors) {
+                nodes[j].unresolved -= 1;
+                if nodes[j].unresolved == 0 {
+                    resolved.push(j);
+                }
+            }
+        }
+        log::info!(
+            target: "solver::retrograde",
+            positions = nodes.len(),
+            elapsed:? = started_at.elapsed();
+            "solved"
+        );
+
+        Retrograde {
+            values: keys
+                .into_iter()
+                .zip(nodes.into_iter().map(|node| node.value))
+                .collect(),
+        }
+    }
+
+    /// the canonical state of every node, and the nodes with their edges and predecessors
+    fn enumerate(initial_state: &game::State) -> (Vec<crate::EncodedState>, Vec<Node>) {
+        let mut ids = HashMap::new();
+        let mut keys = vec![];
+        let mut nodes: Vec<Node> = vec![];
+        let mut states = vec![];
+
+        let (canonical, _) = crate::EncodedState::new(initial_state).canonical();
+        ids.insert(canonical, 0);
+        keys.push(canonical);
+        states.push(initial_state.clone());
+        // BFS, where the ids are in the order of discovery
+        let mut i = 0;
+        while i < states.len() {
+            let s = states[i].clone();
+            let mut edges = vec![];
+            for act in s.all_actions() {
+                let (s_next, result) = s.step(&act);
+                let edge = match result {
+                    game::Result::Win => Edge::Terminal(crate::Value::WIN(1)),
+                    game::Result::Draw => Edge::Terminal(crate::Value::DRAW),
+                    game::Result::WaitingNextAction => {
+                        let (canonical, _) = crate::EncodedState::new(&s_next).canonical();
+                        let is_same_player = s_next.player_to_act == s.player_to_act;
+                        let node = *ids.entry(canonical).or_insert_with(|| {
+                            keys.push(canonical);
+                            states.push(s_next);
+                            keys.len() - 1
+                        });
+                        Edge::To {
+                            node,
+                            is_same_player,
+                        }
+                    }
+                };
+                edges.push(edge);
+            }
+            nodes.push(Node {
+                unresolved: edges
+                    .iter()
+                    .filter(|edge| matches!(edge, Edge::To { .. }))
+                    .count(),
+                edges,
+                predecessors: vec![],
+                value: crate::Value::new(),
+            });
+            i += 1;
+        }
+
+        let edges_to = nodes
+            .iter()
+            .enumerate()
+            .flat_map(|(i, node)| {
+                node.edges.iter().filter_map(move |edge| match *edge {
+                    Edge::To { node, .. } => Some((node, i)),
+                    Edge::Terminal(_) => None,
+                })
+            })
+            .collect::<Vec<_>>();
+        for (node, predecessor) in edges_to {
+            nodes[node].predecessors.push(predecessor);
+        }
+        (keys, nodes)
+    }
+
+    /// the number of positions in the table, up to symmetry
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// the exact value of `state`, or `None` if `state` is not reachable from the initial state
+    pub fn value(&self, state: &game::State) -> Option<crate::Value> {
+        let (canonical, _) = crate::EncodedState::new(state).canonical();
+        self.values.get(&canonical).copied()
+    }
+
+    /// the exact value of taking `action` at `state`, see `Retrograde::value`
+    pub fn action_value(&self, state: &game::State, action: &game::Action) -> Option<crate::Value> {
+        let (s_next, result) = state.step(action);
+        match result {
+            game::Result::Win => Some(crate::Value::WIN(1)),
+            game::Result::Draw => Some(crate::Value::DRAW),
+            game::Result::WaitingNextAction => Some(
+                self.value(&s_next)?
+                    .through(s_next.player_to_act == state.player_to_act),
+            ),
+        }
+    }
+}
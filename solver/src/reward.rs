@@ -1,31 +1,129 @@
 #[derive(
     Default,
-    // sane defaults for unit-like enums:
+    // sane defaults for value objects:
     Clone,
     Copy,
-    derive_more::Display,
     Debug,
-    // sortable:
+    // hashable:
     PartialEq,
     Eq,
-    PartialOrd,
-    Ord,
+    Hash,
+    // serializable:
+    serde::Serialize,
+    serde::Deserialize,
 )]
+#[allow(clippy::upper_case_acronyms)]
 pub enum Value {
     #[default]
     UNDEFINED,
-    LOSE,
+    LOSE(u32), // the number of plies until losing
     DRAW,
-    WIN,
+    WIN(u32), // the number of plies until winning
 }
 
 impl Value {
+    // bounds of all values, e.g. for alpha-beta pruning:
+    // note: the result cannot be reached in 0 plies
+    pub const MIN: Value = Value::LOSE(0);
+    pub const MAX: Value = Value::WIN(0);
+
     pub fn new() -> Value {
         Value::default()
     }
+
+    /// the same outcome seen by the opponent
+    pub fn opposite(&self) -> Value {
+        match *self {
+            Value::UNDEFINED => Value::UNDEFINED,
+            Value::LOSE(plies) => Value::WIN(plies),
+            Value::DRAW => Value::DRAW,
+            Value::WIN(plies) => Value::LOSE(plies),
+        }
+    }
+
+    /// the same outcome reached `plies` plies later
+    pub fn later(&self, plies: u32) -> Value {
+        match *self {
+            Value::LOSE(n) => Value::LOSE(n + plies),
+            Value::WIN(n) => Value::WIN(n + plies),
+            value => value,
+        }
+    }
+
+    /// e.g. `WIN(1)` and `WIN(3)` are the same outcome, reached at different plies
+    pub fn is_same_outcome(&self, other: &Value) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    /// inverse of `Value::later`, saturating at `Value::MIN` and `Value::MAX`
+    pub fn earlier(&self, plies: u32) -> Value {
+        match *self {
+            Value::LOSE(n) => Value::LOSE(n.saturating_sub(plies)),
+            Value::WIN(n) => Value::WIN(n.saturating_sub(plies)),
+            value => value,
+        }
+    }
+
+    /// the value of an action leading to a state of value `self` (for the player to act there), which is 1 ply later
+    ///
+    /// note: the same player can act in consecutive states, see `game::TURN_SCHEDULE`
+    pub fn through(&self, is_same_player: bool) -> Value {
+        if is_same_player {
+            self.later(1)
+        } else {
+            self.opposite().later(1)
+        }
+    }
+
+    /// the window at the next state for the value of an action between `alpha` and `beta`, i.e. the inverse of
+    /// `Value::through`, e.g. for alpha-beta pruning
+    pub fn window_through(alpha: Value, beta: Value, is_same_player: bool) -> (Value, Value) {
+        let (alpha, beta) = (alpha.earlier(1), beta.earlier(1));
+        if is_same_player {
+            (alpha, beta)
+        } else {
+            (beta.opposite(), alpha.opposite())
+        }
+    }
+}
+
+/// UNDEFINED < LOSE < DRAW < WIN, where slower losses and faster wins are better
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let rank = |value: &Value| match *value {
+            Value::UNDEFINED => (0, 0),
+            Value::LOSE(plies) => (1, plies as i64),
+            Value::DRAW => (2, 0),
+            Value::WIN(plies) => (3, -(plies as i64)),
+        };
+        rank(self).cmp(&rank(other))
+    }
 }
 
-pub struct Reward {
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// e.g. `WIN(3)` means winning in 3 plies
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::UNDEFINED => write!(f, "UNDEFINED"),
+            Value::LOSE(plies) => write!(f, "LOSE({plies})"),
+            Value::DRAW => write!(f, "DRAW"),
+            Value::WIN(plies) => write!(f, "WIN({plies})"),
+        }
+    }
+}
+
+#[derive(
+    // serializable:
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub(crate) struct Reward {
     // what the agent knows must happen:
     pub to_encoded_state: crate::EncodedState,
     pub result: game::Result,
@@ -40,12 +138,12 @@ pub struct Reward {
 impl Reward {
     pub fn new(to: &crate::EncodedState, result: &game::Result) -> Reward {
         Reward {
-            to_encoded_state: to.clone(),
+            to_encoded_state: *to,
             result: *result,
             value: match result {
                 game::Result::WaitingNextAction => crate::Value::new(),
                 game::Result::Draw => crate::Value::DRAW,
-                game::Result::Win => crate::Value::WIN,
+                game::Result::Win => crate::Value::WIN(1),
             },
             // note: curr_epoch should start from 1
             last_visited_at: 0,
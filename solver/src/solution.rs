@@ -0,0 +1,302 @@
+use once_cell::sync::Lazy;
+
+/// the trained agents of both players, i.e. the solved game from some initial state
+pub struct Solution {
+    pub initial_state: game::State, // which the tables are solved from, i.e. they cover the states reachable from it
+    pub p1_agent: crate::Agent,
+    pub p2_agent: crate::Agent,
+    pub engine: Engine, // which solved the tables, see `Engine::is_exhaustive`
+    pub training_report: Option<crate::TrainingReport>, // only when solved by `Engine::Trainer`
+}
+
+#[derive(
+    Default,
+    // sane defaults for unit-like enums:
+    Clone,
+    Copy,
+    derive_more::Display,
+    Debug,
+    // hashable:
+    PartialEq,
+    Eq,
+    Hash,
+    // serializable:
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum Engine {
+    #[default]
+    Trainer, // see `crate::Agent::new_trained`
+    Negamax,    // see `crate::Agent::new_searched`
+    Gaps,       // see `crate::GapSolver`, only for the initial state of a single-row board
+    Retrograde, // see `crate::Agent::new_retrograde`
+}
+
+/// `trainer`, `negamax`, `gaps` or `retrograde`
+impl std::str::FromStr for Engine {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Engine> {
+        match s {
+            "trainer" => Ok(Engine::Trainer),
+            "negamax" => Ok(Engine::Negamax),
+            "gaps" => Ok(Engine::Gaps),
+            "retrograde" => Ok(Engine::Retrograde),
+            _ => anyhow::bail!(
+                "unknown engine `{s}`, expected `trainer`, `negamax`, `gaps` or `retrograde`"
+            ),
+        }
+    }
+}
+
+/// configurable via env `SOLVER_ENGINE`
+pub static ENGINE: Lazy<Engine> = Lazy::new(|| match std::env::var("SOLVER_ENGINE") {
+    Ok(val) => val.parse::<Engine>().unwrap_or_else(|err| panic!("{err}")),
+    Err(_) => Engine::new(),
+});
+
+impl Engine {
+    pub fn new() -> Engine {
+        Engine::default()
+    }
+
+    /// whether every action of every reachable state has its exact value, unlike `Engine::Trainer`, which is only
+    /// exact under optimal play, see `Solution::evaluate`
+    pub fn is_exhaustive(&self) -> bool {
+        !matches!(self, Engine::Trainer)
+    }
+}
+
+/// the number of threads to solve with, configurable via env `SOLVER_THREADS` (all cores by default)
+///
+/// note: the solution does not depend on the number of threads, where the enumeration of the reachable states (and
+/// the search of each of them by `Engine::Negamax`) and each epoch of `Engine::Trainer` are parallel, but the backward
+/// propagation of `Engine::Retrograde` is not
+pub static THREADS: Lazy<usize> = Lazy::new(|| match std::env::var("SOLVER_THREADS") {
+    Ok(val) => (|| -> anyhow::Result<usize> {
+        let threads = val.parse::<usize>()?;
+        anyhow::ensure!(threads > 0, "there should be at least 1 thread");
+        Ok(threads)
+    })()
+    .unwrap_or_else(|err| panic!("invalid SOLVER_THREADS `{val}`: {err}")),
+    Err(_) => std::thread::available_parallelism().map_or(1, |n| n.get()),
+});
+
+/// the maximum number of epochs to train for, configurable via env `SOLVER_MAX_EPOCHS` (unlimited by default)
+pub static MAX_EPOCHS: Lazy<Option<u32>> = Lazy::new(|| {
+    std::env::var("SOLVER_MAX_EPOCHS").ok().map(|val| {
+        (|| -> anyhow::Result<u32> {
+            let max_epochs = val.parse::<u32>()?;
+            anyhow::ensure!(max_epochs > 0, "there should be at least 1 epoch");
+            Ok(max_epochs)
+        })()
+        .unwrap_or_else(|err| panic!("invalid SOLVER_MAX_EPOCHS `{val}`: {err}"))
+    })
+});
+
+/// solve the game for all states reachable from `initial_state` (e.g. `game::INITIAL_STATE`) with `crate::ENGINE`
+///
+/// fails if the training does not converge within `crate::MAX_EPOCHS`
+pub fn solve(initial_state: &game::State) -> anyhow::Result<Solution> {
+    solve_with(*ENGINE, initial_state)
+}
+
+/// solve with `engine` instead of `crate::ENGINE`, see `solve`
+///
+/// fails for `Engine::Gaps`, which only searches, see `crate::GapSolver`, or if the board is too large to be encoded,
+/// see `crate::EncodedState::ensure_fits`
+pub fn solve_with(engine: Engine, initial_state: &game::State) -> anyhow::Result<Solution> {
+    crate::EncodedState::ensure_fits()?;
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(*THREADS)
+        .build()
+        .expect("failed to start the solver threads");
+    pool.install(|| match engine {
+        Engine::Trainer => {
+            let (p1_agent, p2_agent, training_report) =
+                crate::Agent::new_trained(initial_state, *MAX_EPOCHS)?;
+            Ok(Solution {
+                initial_state: initial_state.clone(),
+                p1_agent,
+                p2_agent,
+                engine,
+                training_report: Some(training_report),
+            })
+        }
+        Engine::Negamax => {
+            let (p1_agent, p2_agent) = crate::Agent::new_searched(initial_state);
+            Ok(Solution {
+                initial_state: initial_state.clone(),
+                p1_agent,
+                p2_agent,
+                engine,
+                training_report: None,
+            })
+        }
+        Engine::Retrograde => {
+            let (p1_agent, p2_agent) = crate::Agent::new_retrograde(initial_state);
+            Ok(Solution {
+                initial_state: initial_state.clone(),
+                p1_agent,
+                p2_agent,
+                engine,
+                training_report: None,
+            })
+        }
+        // note: `crate::GapSolver` keeps no tables of whole positions, so there is no solution to return
+        Engine::Gaps => anyhow::bail!("the gap solver has no tables, use `GapSolver` instead"),
+    })
+}
+
+impl Solution {
+    pub fn agent(&self, player: game::Player) -> &crate::Agent {
+        match player {
+            game::Player::P1 => &self.p1_agent,
+            game::Player::P2 => &self.p2_agent,
+        }
+    }
+
+    /// the value of `state` for the player to act, or `None` if `state` is not reachable from the solved initial state
+    /// or the game is already over
+    ///
+    /// note: values are exact for states reachable from the solved initial state under optimal play,
+    /// solve from `state` itself for an exact value of any other state
+    pub fn evaluate(&self, state: &game::State) -> Option<crate::Value> {
+        let agent = self.agent(state.player_to_act);
+        let encoded_state = crate::EncodedState::new(state);
+        agent
+            .contains(&encoded_state)
+            .then(|| agent.max_value(&encoded_state))
+    }
+
+    /// the value of every legal action at `state` for the player to act, see `Solution::evaluate`
+    pub fn action_values(&self, state: &game::State) -> Option<Vec<(game::Action, crate::Value)>> {
+        let agent = self.agent(state.player_to_act);
+        let encoded_state = crate::EncodedState::new(state);
+        agent.contains(&encoded_state).then(|| {
+            let mut v = state
+                .all_actions()
+                .into_iter()
+                .map(|act| (act, agent.reward(&encoded_state, &act).value))
+                .collect::<Vec<_>>();
+            v.sort_unstable_by_key(|(act, _)| *act);
+            v
+        })
+    }
+
+    /// the optimal actions at `state` for the player to act, see `Solution::evaluate`
+    pub fn best_moves(&self, state: &game::State) -> Option<Vec<game::Action>> {
+        let agent = self.agent(state.player_to_act);
+        let encoded_state = crate::EncodedState::new(state);
+        agent.contains(&encoded_state).then(|| {
+            let mut v = agent.optimal_actions(&encoded_state);
+            v.sort_unstable();
+            v
+        })
+    }
+}
+
+/// how often a policy picks an action as good as the solved one, see `Solution::compare`
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+    Copy,
+    Debug,
+    Default,
+)]
+pub struct Comparison {
+    pub states: usize,
+    pub optimal: usize, // the picked action has the best value, i.e. the same result just as fast
+    pub same_outcome: usize, // the picked action still wins, draws or loses like the best one
+    pub unknown: usize, // the policy picks nothing, e.g. the state is never seen in training
+}
+
+impl std::fmt::Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let percent = |n: usize| 100.0 * n as f64 / self.states.max(1) as f64;
+        write!(
+            f,
+            "{} states, optimal in {} ({:.1}%), the same outcome in {} ({:.1}%)",
+            self.states,
+            self.optimal,
+            percent(self.optimal),
+            self.same_outcome,
+            percent(self.same_outcome)
+        )?;
+        // only worth showing for policies which cannot pick everywhere
+        if self.unknown > 0 {
+            write!(
+                f,
+                ", nothing picked in {} ({:.1}%)",
+                self.unknown,
+                percent(self.unknown)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Solution {
+    /// compare the actions picked by `choose` with the action values of every state reachable from `initial_state`
+    /// (up to symmetry)
+    ///
+    /// fails unless solved by an exhaustive engine, see `Solution::ensure_exhaustive`
+    pub fn compare(
+        &self,
+        initial_state: &game::State,
+        mut choose: impl FnMut(&game::State) -> Option<game::Action>,
+    ) -> anyhow::Result<Comparison> {
+        self.ensure_exhaustive()?;
+        let mut comparison = Comparison::default();
+        let mut explored = std::collections::HashSet::new();
+        let mut unexplored = vec![initial_state.clone()];
+        while let Some(s) = unexplored.pop() {
+            if !explored.insert(crate::EncodedState::new(&s).canonical().0) {
+                continue;
+            }
+            let Some(action_values) = self.action_values(&s) else {
+                continue;
+            };
+            if let Some(best_value) = action_values.iter().map(|(_, value)| *value).max() {
+                comparison.states += 1;
+                match choose(&s) {
+                    Some(act) => {
+                        let value = action_values
+                            .iter()
+                            .find(|(a, _)| *a == act)
+                            .map(|(_, value)| *value)
+                            .unwrap();
+                        if value == best_value {
+                            comparison.optimal += 1;
+                        }
+                        if value.is_same_outcome(&best_value) {
+                            comparison.same_outcome += 1;
+                        }
+                    }
+                    None => comparison.unknown += 1,
+                }
+            }
+            for act in s.all_actions() {
+                let (s_next, result) = s.step(&act);
+                if matches!(result, game::Result::WaitingNextAction) {
+                    unexplored.push(s_next);
+                }
+            }
+        }
+        Ok(comparison)
+    }
+
+    /// fails unless every action has its exact value, as needed by anything reading the value of other actions than
+    /// the best ones, e.g. `Solution::compare` and `Solution::table_rows`
+    ///
+    /// note: the library never solves again with another engine, and neither do the binaries, e.g. the `query` binary
+    /// only defaults to `Engine::Negamax` instead
+    pub fn ensure_exhaustive(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.engine.is_exhaustive(),
+            "the tables solved by {:?} are only exact under optimal play, solve by Negamax or Retrograde instead",
+            self.engine
+        );
+        Ok(())
+    }
+}
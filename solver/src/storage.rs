@@ -0,0 +1,112 @@
+use std::io::{BufReader, BufWriter, Read, Write};
+
+const MAGIC: [u8; 4] = *b"C2SL";
+// note: bump on any change in the serialized layout of `Header` or `crate::Agent`
+const VERSION: u32 = 8;
+
+/// the rules which the saved agents are trained for
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+    Debug,
+    // hashable:
+    PartialEq,
+    Eq,
+    // serializable:
+    serde::Serialize,
+    serde::Deserialize,
+)]
+struct Rules {
+    rows: usize,
+    cols: usize,
+    neighbours: Vec<Vec<usize>>,
+    turn_schedule: Vec<usize>,
+    allow_pass: bool,
+}
+
+impl Rules {
+    fn current() -> Rules {
+        Rules {
+            rows: game::BOARD.rows,
+            cols: game::BOARD.cols,
+            neighbours: game::BOARD.neighbours.clone(),
+            turn_schedule: game::TURN_SCHEDULE.clone(),
+            allow_pass: *game::ALLOW_PASS,
+        }
+    }
+}
+
+impl std::fmt::Display for Rules {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.neighbours == game::Board::grid(self.rows, self.cols).neighbours {
+            write!(f, "{}x{} board", self.rows, self.cols)?;
+        } else {
+            write!(f, "graph board with {} cells", self.neighbours.len())?;
+        }
+        write!(
+            f,
+            ", turn schedule {} and pass {}",
+            self.turn_schedule
+                .iter()
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+            if self.allow_pass {
+                "allowed"
+            } else {
+                "not allowed"
+            }
+        )
+    }
+}
+
+impl crate::Solution {
+    /// save the trained agents, which can only be loaded back under the same rules
+    ///
+    /// the file starts with a header, i.e. the magic bytes, the version and the rules, then the initial state and the
+    /// engine
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> anyhow::Result<()> {
+        let mut writer = BufWriter::new(std::fs::File::create(path)?);
+        writer.write_all(&MAGIC)?;
+        bincode::serialize_into(&mut writer, &VERSION)?;
+        bincode::serialize_into(&mut writer, &Rules::current())?;
+        bincode::serialize_into(&mut writer, &self.initial_state)?;
+        bincode::serialize_into(&mut writer, &self.engine)?;
+        bincode::serialize_into(&mut writer, &self.p1_agent)?;
+        bincode::serialize_into(&mut writer, &self.p2_agent)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// load the trained agents saved by `Solution::save`, validating that they are trained for the current rules
+    ///
+    /// note: they can be solved from any initial state, see `Solution::initial_state`
+    pub fn load(path: impl AsRef<std::path::Path>) -> anyhow::Result<crate::Solution> {
+        let mut reader = BufReader::new(std::fs::File::open(path)?);
+
+        let mut magic = [0u8; 4];
+        anyhow::ensure!(
+            reader.read_exact(&mut magic).is_ok() && magic == MAGIC,
+            "not a saved solution"
+        );
+        let version: u32 = bincode::deserialize_from(&mut reader)?;
+        anyhow::ensure!(
+            version == VERSION,
+            "unsupported version {version} (expected {VERSION})"
+        );
+        let saved_rules: Rules = bincode::deserialize_from(&mut reader)?;
+        let rules = Rules::current();
+        anyhow::ensure!(
+            saved_rules == rules,
+            "the solution is saved for {saved_rules} but the current rules are {rules}"
+        );
+
+        Ok(crate::Solution {
+            initial_state: bincode::deserialize_from(&mut reader)?,
+            engine: bincode::deserialize_from(&mut reader)?,
+            p1_agent: bincode::deserialize_from(&mut reader)?,
+            p2_agent: bincode::deserialize_from(&mut reader)?,
+            training_report: None,
+        })
+    }
+}
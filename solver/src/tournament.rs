@@ -0,0 +1,341 @@
+use once_cell::sync::Lazy;
+use rand::{Rng, SeedableRng};
+
+/// configurable via env `TOURNAMENT_BOTS`, e.g. `random,greedy,depth:2,mcts:100,solver` (the default)
+pub static TOURNAMENT_BOTS: Lazy<Vec<crate::Bot>> = Lazy::new(|| {
+    let val = std::env::var("TOURNAMENT_BOTS")
+        .unwrap_or_else(|_| "random,greedy,depth:2,mcts:100,solver".to_string());
+    val.split(',')
+        .map(|s| s.trim().parse::<crate::Bot>())
+        .collect::<anyhow::Result<Vec<_>>>()
+        .unwrap_or_else(|err| panic!("invalid TOURNAMENT_BOTS `{val}`: {err}"))
+});
+
+/// the number of games between every two bots, configurable via env `TOURNAMENT_GAMES` (`20` by default)
+pub static TOURNAMENT_GAMES: Lazy<u32> = Lazy::new(|| match std::env::var("TOURNAMENT_GAMES") {
+    Ok(val) => (|| -> anyhow::Result<u32> {
+        let games = val.parse::<u32>()?;
+        anyhow::ensure!(games > 0, "there should be at least 1 game");
+        Ok(games)
+    })()
+    .unwrap_or_else(|err| panic!("invalid TOURNAMENT_GAMES `{val}`: {err}")),
+    Err(_) => 20,
+});
+
+/// configurable via env `TOURNAMENT_SEED`
+pub static TOURNAMENT_SEED: Lazy<u64> = Lazy::new(|| match std::env::var("TOURNAMENT_SEED") {
+    Ok(val) => val
+        .parse::<u64>()
+        .unwrap_or_else(|err| panic!("invalid TOURNAMENT_SEED `{val}`: {err}")),
+    Err(_) => 0,
+});
+
+/// the games of one bot against another, from the perspective of the former
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+    Copy,
+    Debug,
+    Default,
+)]
+pub struct Record {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl Record {
+    pub fn games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    /// a win counts 1 and a draw counts 1/2
+    pub fn score(&self) -> f64 {
+        self.wins as f64 + 0.5 * self.draws as f64
+    }
+}
+
+impl std::ops::Add for Record {
+    type Output = Record;
+
+    fn add(self, other: Record) -> Record {
+        Record {
+            wins: self.wins + other.wins,
+            draws: self.draws + other.draws,
+            losses: self.losses + other.losses,
+        }
+    }
+}
+
+/// e.g. `12-5-3` for 12 wins, 5 draws and 3 losses
+impl std::fmt::Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}-{}", self.wins, self.draws, self.losses)
+    }
+}
+
+/// an Elo estimate with its 95% confidence interval, i.e. `elo ± margin`
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+    Copy,
+    Debug,
+)]
+pub struct Rating {
+    pub elo: f64,
+    pub margin: f64,
+}
+
+/// a round-robin tournament, where every two bots play `games` games alternating the first player
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+    Debug,
+)]
+pub struct Tournament {
+    pub bots: Vec<crate::Bot>,
+    pub games: u32,
+    pub seed: u64, // the same seed (and iteration budgets) always plays the same games
+}
+
+/// the crosstable and the ratings of a played `Tournament`
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+    Debug,
+)]
+pub struct TournamentReport {
+    pub bots: Vec<crate::Bot>,
+    pub records: Vec<Vec<Record>>, // `records[i][j]` are the games of `bots[i]` against `bots[j]`
+    pub ratings: Vec<Rating>,
+}
+
+impl Tournament {
+    /// configured via env `TOURNAMENT_BOTS`, `TOURNAMENT_GAMES` and `TOURNAMENT_SEED`
+    pub fn new() -> Tournament {
+        Tournament {
+            bots: TOURNAMENT_BOTS.clone(),
+            games: *TOURNAMENT_GAMES,
+            seed: *TOURNAMENT_SEED,
+        }
+    }
+
+    /// play every game from `game::INITIAL_STATE`
+    pub fn run(&self) -> anyhow::Result<TournamentReport> {
+        anyhow::ensure!(
+            self.bots.len() >= 2,
+            "a tournament needs at least 2 bots, but got {}",
+            self.bots.len()
+        );
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(self.seed);
+        // note: every bot keeps its state (e.g. a transposition table) across all its games
+        let mut policies = self
+            .bots
+            .iter()
+            .map(|bot| bot.new_policy(rng.gen()))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let n = self.bots.len();
+        let mut records = vec![vec![Record::default(); n]; n];
+        let pairings = (0..n).flat_map(|i| (i + 1..n).map(move |j| (i, j)));
+        for (i, j) in pairings {
+            let (left, right) = policies.split_at_mut(j);
+            let (bot_i, bot_j) = (&mut left[i], &mut right[0]);
+            for game in 0..self.games {
+                let bot_i_first = game % 2 == 0;
+                let winner = if bot_i_first {
+                    Tournament::play(bot_i.as_mut(), bot_j.as_mut())
+                } else {
+                    Tournament::play(bot_j.as_mut(), bot_i.as_mut())
+                };
+                let bot_i_won = match winner {
+                    Some(game::Player::P1) => Some(bot_i_first),
+                    Some(game::Player::P2) => Some(!bot_i_first),
+                    None => None,
+                };
+                match bot_i_won {
+                    Some(true) => {
+                        records[i][j].wins += 1;
+                        records[j][i].losses += 1;
+                    }
+                    Some(false) => {
+                        records[i][j].losses += 1;
+                        records[j][i].wins += 1;
+                    }
+                    None => {
+                        records[i][j].draws += 1;
+                        records[j][i].draws += 1;
+                    }
+                }
+            }
+            log::info!(
+                target: "solver::tournament",
+                record:% = records[i][j];
+                "{} vs {}",
+                self.bots[i],
+                self.bots[j]
+            );
+        }
+
+        Ok(TournamentReport {
+            bots: self.bots.clone(),
+            ratings: TournamentReport::ratings(&records),
+            records,
+        })
+    }
+
+    /// play a game from `game::INITIAL_STATE`, returning the winner (if any)
+    fn play(p1: &mut dyn game::Policy, p2: &mut dyn game::Policy) -> Option<game::Player> {
+        let mut state = game::INITIAL_STATE.clone();
+        loop {
+            let act = match state.player_to_act {
+                game::Player::P1 => p1.choose(&state),
+                game::Player::P2 => p2.choose(&state),
+            };
+            let (s_next, result) = state.step(&act);
+            match result {
+                game::Result::Win => return Some(state.player_to_act),
+                game::Result::Draw => return None,
+                game::Result::WaitingNextAction => state = s_next,
+            }
+        }
+    }
+}
+
+impl Default for Tournament {
+    fn default() -> Self {
+        Tournament::new()
+    }
+}
+
+impl TournamentReport {
+    /// the total games of `bots[i]` against everyone
+    pub fn total(&self, i: usize) -> Record {
+        self.records[i]
+            .iter()
+            .fold(Record::default(), |acc, record| acc + *record)
+    }
+
+    /// the maximum likelihood Elo ratings (Bradley-Terry, counting a draw as half a win) averaging 1500
+    ///
+    /// note: every pairing also counts one virtual draw, so that a perfect (or zero) score has a finite rating
+    fn ratings(records: &[Vec<Record>]) -> Vec<Rating> {
+        const ELO_PER_NEPER: f64 = 400.0 / std::f64::consts::LN_10;
+        let n = records.len();
+        let games = |i: usize, j: usize| (records[i][j].games() + 1) as f64;
+        let scores = (0..n)
+            .map(|i| {
+                (0..n)
+                    .filter(|&j| j != i)
+                    .map(|j| records[i][j].score() + 0.5)
+                    .sum::<f64>()
+            })
+            .collect::<Vec<_>>();
+
+        // the minorization-maximization algorithm of Hunter (2004), on strengths `gamma = 10^(elo / 400)`
+        let mut gammas = vec![1.0; n];
+        for _ in 0..10000 {
+            let mut next = (0..n)
+                .map(|i| {
+                    scores[i]
+                        / (0..n)
+                            .filter(|&j| j != i)
+                            .map(|j| games(i, j) / (gammas[i] + gammas[j]))
+                            .sum::<f64>()
+                })
+                .collect::<Vec<_>>();
+            let log_mean = next.iter().map(|gamma: &f64| gamma.ln()).sum::<f64>() / n as f64;
+            next.iter_mut().for_each(|gamma| *gamma /= log_mean.exp());
+            let converged = next
+                .iter()
+                .zip(gammas.iter())
+                .all(|(a, b)| (a.ln() - b.ln()).abs() < 1e-12);
+            gammas = next;
+            if converged {
+                break;
+            }
+        }
+
+        (0..n)
+            .map(|i| {
+                // the standard error from the Fisher information of the rating of `i` alone
+                let information = (0..n)
+                    .filter(|&j| j != i)
+                    .map(|j| {
+                        let p = gammas[i] / (gammas[i] + gammas[j]);
+                        games(i, j) * p * (1.0 - p)
+                    })
+                    .sum::<f64>();
+                Rating {
+                    elo: 1500.0 + ELO_PER_NEPER * gammas[i].ln(),
+                    margin: 1.96 * ELO_PER_NEPER / information.sqrt(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// the crosstable (wins-draws-losses of the row against the column), then the standings by rating
+impl std::fmt::Display for TournamentReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names = self
+            .bots
+            .iter()
+            .map(|bot| bot.to_string())
+            .collect::<Vec<_>>();
+        let width = names
+            .iter()
+            .map(|name| name.len())
+            .max()
+            .unwrap_or_default();
+        let cell_width = self
+            .records
+            .iter()
+            .flatten()
+            .map(|record| record.to_string().len())
+            .chain(names.iter().map(|name| name.len()))
+            .max()
+            .unwrap_or_default();
+
+        write!(f, "{:width$}", "")?;
+        for name in names.iter() {
+            write!(f, "  {name:>cell_width$}")?;
+        }
+        for (i, name) in names.iter().enumerate() {
+            write!(f, "\n{name:width$}")?;
+            for (j, record) in self.records[i].iter().enumerate() {
+                let cell = if i == j {
+                    "-".to_string()
+                } else {
+                    record.to_string()
+                };
+                write!(f, "  {cell:>cell_width$}")?;
+            }
+        }
+
+        let mut standings = (0..self.bots.len()).collect::<Vec<_>>();
+        standings.sort_by(|&a, &b| self.ratings[b].elo.total_cmp(&self.ratings[a].elo));
+        write!(
+            f,
+            "\n\n{:width$}  {:>5}  {:>5}  {:>5}  {:>6}  {:>6}  {:>11}",
+            "bot", "games", "wins", "draws", "losses", "score", "elo"
+        )?;
+        for i in standings {
+            let total = self.total(i);
+            let Rating { elo, margin } = self.ratings[i];
+            write!(
+                f,
+                "\n{:width$}  {:>5}  {:>5}  {:>5}  {:>6}  {:>6}  {:>11}",
+                names[i],
+                total.games(),
+                total.wins,
+                total.draws,
+                total.losses,
+                format!("{:.1}%", 100.0 * total.score() / total.games() as f64),
+                format!("{elo:.0} ± {margin:.0}"),
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+/// what happened to the trained agent in one epoch, see `crate::Agent::new_trained`
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+    Debug,
+)]
+pub struct EpochStats {
+    pub epoch: u32,
+    pub player: game::Player, // the trained player
+    pub states_visited: usize,
+    pub values_changed: usize,
+    pub undefined_states: usize, // states of both players without any defined value yet
+    pub elapsed: std::time::Duration,
+}
+
+impl EpochStats {
+    /// collect the stats of `agent` right after training it in `epoch`
+    pub(crate) fn new(
+        epoch: u32,
+        player: game::Player,
+        agent: &crate::Agent,
+        opponent: &crate::Agent,
+        elapsed: std::time::Duration,
+    ) -> EpochStats {
+        EpochStats {
+            epoch,
+            player,
+            states_visited: agent.states_visited_at(epoch),
+            values_changed: agent.values_updated_at(epoch),
+            undefined_states: agent.undefined_states() + opponent.undefined_states(),
+            elapsed,
+        }
+    }
+}
+
+/// the stats of every epoch until the training converges
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+    Debug,
+    Default,
+)]
+pub struct TrainingReport {
+    pub epochs: Vec<EpochStats>,
+}
+
+impl TrainingReport {
+    pub fn new() -> TrainingReport {
+        TrainingReport::default()
+    }
+
+    pub fn elapsed(&self) -> std::time::Duration {
+        self.epochs.iter().map(|stats| stats.elapsed).sum()
+    }
+}
+
+/// a summary table with a row for each epoch
+impl std::fmt::Display for TrainingReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>5}  {:>6}  {:>14}  {:>14}  {:>16}  {:>10}",
+            "epoch", "player", "states visited", "values changed", "undefined states", "elapsed"
+        )?;
+        for stats in self.epochs.iter() {
+            write!(
+                f,
+                "\n{:>5}  {:>6}  {:>14}  {:>14}  {:>16}  {:>10}",
+                stats.epoch,
+                stats.player.to_string(),
+                stats.states_visited,
+                stats.values_changed,
+                stats.undefined_states,
+                format!("{:.1?}", stats.elapsed),
+            )?;
+        }
+        Ok(())
+    }
+}
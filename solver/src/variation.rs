@@ -0,0 +1,101 @@
+/// one ply of a `PrincipalVariation`
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+)]
+pub struct VariationPly {
+    pub state: game::State,
+    pub value: crate::Value, // of `state` for the player to act
+    pub action: game::Action,
+    pub alternatives: Vec<game::Action>, // other actions just as good as `action`, sorted
+}
+
+/// the expected line of play until the game is over under optimal play by both players
+///
+/// note: at a branching point the first optimal action is followed, and the others are listed as alternatives
+#[derive(
+    // sane defaults for value objects:
+    Clone,
+)]
+pub struct PrincipalVariation {
+    pub plies: Vec<VariationPly>,
+    pub winner: Option<game::Player>, // `None` for a draw
+}
+
+impl PrincipalVariation {
+    /// follow `solve` (the value and the optimal actions of a state, or `None` if unknown) from `initial_state`,
+    /// or `None` if any state on the way is unknown or the game is already over
+    pub fn new(
+        initial_state: &game::State,
+        mut solve: impl FnMut(&game::State) -> Option<(crate::Value, Vec<game::Action>)>,
+    ) -> Option<PrincipalVariation> {
+        let mut plies = vec![];
+        let mut state = initial_state.clone();
+        loop {
+            let (value, mut best_moves) = solve(&state)?;
+            best_moves.sort_unstable();
+            let (&action, alternatives) = best_moves.split_first()?;
+            let (s_next, result) = state.step(&action);
+            let player = state.player_to_act;
+            plies.push(VariationPly {
+                state,
+                value,
+                action,
+                alternatives: alternatives.to_vec(),
+            });
+            match result {
+                game::Result::Win => {
+                    return Some(PrincipalVariation {
+                        plies,
+                        winner: Some(player),
+                    })
+                }
+                game::Result::Draw => {
+                    return Some(PrincipalVariation {
+                        plies,
+                        winner: None,
+                    })
+                }
+                game::Result::WaitingNextAction => state = s_next,
+            }
+        }
+    }
+}
+
+/// a line per ply like the solved tables, e.g. `P1 _,_,_,_ [WIN(3)] -> (1) or (2)`, then the result
+impl std::fmt::Display for PrincipalVariation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for ply in self.plies.iter() {
+            write!(
+                f,
+                "{} {} [{}] -> {}",
+                ply.state.player_to_act,
+                crate::Position(&ply.state),
+                ply.value,
+                ply.action
+            )?;
+            for act in ply.alternatives.iter() {
+                write!(f, " or {act}")?;
+            }
+            writeln!(f)?;
+        }
+        match self.winner {
+            Some(player) => write!(f, "{player} wins"),
+            None => write!(f, "draw"),
+        }
+    }
+}
+
+impl crate::Solution {
+    /// see `PrincipalVariation::new` and `Solution::evaluate`
+    pub fn principal_variation(&self, state: &game::State) -> Option<PrincipalVariation> {
+        PrincipalVariation::new(state, |s| Some((self.evaluate(s)?, self.best_moves(s)?)))
+    }
+}
+
+impl crate::GapSolver {
+    /// see `PrincipalVariation::new`, where every state is searched
+    pub fn principal_variation(&mut self, state: &game::State) -> Option<PrincipalVariation> {
+        PrincipalVariation::new(state, |s| Some((self.value(s), self.best_moves(s))))
+    }
+}
@@ -27,3 +27,70 @@
 3" | TESTING="1" cargo run --quiet --bin cli'
     [[ "$output" = "draw!" ]]
 }
+
+@test "player 2 win with 2 stones per turn (1,_,2,2)" {
+    run bash -c 'echo "0
+2
+3" | TESTING="1" TURN_SCHEDULE="1,2" cargo run --quiet --bin cli'
+    [[ "$output" = "Player 2 wins!" ]]
+}
+
+@test "player 1 win on a grid (b2,c3)" {
+    run bash -c 'echo "b2
+a1
+c3" | TESTING="1" BOARD_ROWS="3" BOARD_SIZE="3" cargo run --quiet --bin cli'
+    [[ "$output" = "Player 1 wins!" ]]
+}
+
+@test "out-of-range columns are retried on a grid (b2,c3)" {
+    run bash -c 'echo "zzzzzzzzzzzzzzzzzzzz1
+d1
+b2
+a1
+c3" | TESTING="1" BOARD_ROWS="3" BOARD_SIZE="3" cargo run --quiet --bin cli'
+    [[ "${lines[0]}" = "you should enter a cell like \`b3\`!" ]]
+    [[ "${lines[1]}" = "you should enter a cell like \`b3\`!" ]]
+    [[ "${lines[2]}" = "Player 1 wins!" ]]
+}
+
+@test "player 1 win on a star (center,leaf)" {
+    run bash -c 'echo "0
+1
+2" | TESTING="1" BOARD_GRAPH="boards/star.txt" cargo run --quiet --bin cli'
+    [[ "$output" = "Player 1 wins!" ]]
+}
+
+@test "draw after consecutive passes (_,_,_,_)" {
+    run bash -c 'echo "pass
+pass" | TESTING="1" ALLOW_PASS="1" cargo run --quiet --bin cli'
+    [[ "$output" = "draw!" ]]
+}
+
+@test "player 1 win after player 2 passes (1,1,_,_)" {
+    run bash -c 'echo "0
+pass
+1" | TESTING="1" ALLOW_PASS="1" cargo run --quiet --bin cli'
+    [[ "$output" = "Player 1 wins!" ]]
+}
+
+@test "player 2 win from a custom starting position (1,2,2,1)" {
+    run bash -c 'echo "1" | TESTING="1" START_POSITION="1,_,2,1" START_PLAYER="2" cargo run --quiet --bin cli'
+    [[ "$output" = "Player 2 wins!" ]]
+}
+
+@test "greedy player 2 blocks then wins (1,2,2,1)" {
+    run bash -c 'echo "0
+3" | TESTING="1" PLAYER2="greedy" cargo run --quiet --bin cli'
+    [[ "$output" = "Player 2 wins!" ]]
+}
+
+@test "perfect player 1 beats greedy player 2" {
+    run bash -c 'TESTING="1" PLAYER1="solver" PLAYER2="greedy" cargo run --quiet --bin cli'
+    [[ "$output" = "Player 1 wins!" ]]
+}
+
+@test "invalid player fails naming it" {
+    run bash -c 'TESTING="1" PLAYER1="foo" cargo run --quiet --bin cli'
+    [[ "$status" -eq 1 ]]
+    [[ "$output" = *"invalid PLAYER1 \`foo\`"* ]]
+}
@@ -0,0 +1,301 @@
+# the value and optimal actions of the initial state, e.g. `_,_,_,_ [WIN(3)] -> {(1), (2)}`
+initial_state_line() {
+    cargo run --quiet --bin solver | sed -n '/^result:/,$p' | grep -m 1 "^$1 \["
+}
+
+@test "negamax agrees with trainer (_,_,_,_)" {
+    trainer="$(SOLVER_ENGINE="trainer" initial_state_line "_,_,_,_")"
+    negamax="$(SOLVER_ENGINE="negamax" initial_state_line "_,_,_,_")"
+    [[ "$trainer" = "_,_,_,_ [WIN(3)] -> {(1), (2)}" ]]
+    [[ "$negamax" = "$trainer" ]]
+}
+
+@test "negamax agrees with trainer with 2 stones per turn (_,_,_,_,_)" {
+    export BOARD_SIZE="5" TURN_SCHEDULE="1,2"
+    trainer="$(SOLVER_ENGINE="trainer" initial_state_line "_,_,_,_,_ +1")"
+    negamax="$(SOLVER_ENGINE="negamax" initial_state_line "_,_,_,_,_ +1")"
+    [[ "$trainer" = "_,_,_,_,_ +1 [LOSE(3)] -> {(0), (1), (2), (3), (4)}" ]]
+    [[ "$negamax" = "$trainer" ]]
+}
+
+@test "retrograde gives the same tables as negamax with 2 stones per turn" {
+    export BOARD_SIZE="7" TURN_SCHEDULE="1,2"
+    negamax="$(SOLVER_ENGINE="negamax" cargo run --quiet --bin solver | sed -n '/^result:/,$p')"
+    retrograde="$(SOLVER_ENGINE="retrograde" cargo run --quiet --bin solver | sed -n '/^result:/,$p')"
+    [[ "$retrograde" = "$negamax" ]]
+}
+
+@test "retrograde solves from a later position (1,_,_,_,_,_)" {
+    export BOARD_SIZE="6" START_POSITION="1,_,_,_,_,_" START_PLAYER="2"
+    retrograde="$(SOLVER_ENGINE="retrograde" initial_state_line "_,_,_,_,_,1")"
+    [[ "$retrograde" = "_,_,_,_,_,1 [DRAW] -> {(4)}" ]]
+}
+
+@test "invalid start position fails naming it (1,_,1,_)" {
+    run bash -c "START_POSITION='1,_,1,_' cargo run --quiet --bin solver"
+    [[ "$status" -ne 0 ]]
+    [[ "$output" = *"invalid START_POSITION \`1,_,1,_\`"* ]]
+    [[ "$output" = *"P1 cannot be to act with 2 stones against 0"* ]]
+}
+
+@test "solver gives the same tables whatever the number of threads" {
+    export BOARD_SIZE="6" SOLVER_ENGINE="negamax"
+    single="$(SOLVER_THREADS="1" cargo run --quiet --bin solver | sed -n '/^result:/,$p')"
+    multi="$(SOLVER_THREADS="4" cargo run --quiet --bin solver | sed -n '/^result:/,$p')"
+    [[ "$multi" = "$single" ]]
+}
+
+@test "trainer gives the same tables whatever the number of threads" {
+    export BOARD_SIZE="7" TURN_SCHEDULE="1,2" SOLVER_ENGINE="trainer"
+    single="$(SOLVER_THREADS="1" cargo run --quiet --bin solver | sed -n '/^result:/,$p')"
+    multi="$(SOLVER_THREADS="4" cargo run --quiet --bin solver | sed -n '/^result:/,$p')"
+    [[ "$multi" = "$single" ]]
+}
+
+@test "saved solution loads back the same tables" {
+    file="$BATS_TEST_TMPDIR/solution.bin"
+    saved="$(SAVE_SOLUTION="$file" cargo run --quiet --bin solver | sed -n '/^result:/,$p')"
+    loaded="$(LOAD_SOLUTION="$file" cargo run --quiet --bin solver | sed -n '/^result:/,$p')"
+    [[ "$loaded" = "$saved" ]]
+}
+
+@test "saved solution cannot be loaded under different rules" {
+    file="$BATS_TEST_TMPDIR/solution.bin"
+    SAVE_SOLUTION="$file" cargo run --quiet --bin solver
+    run bash -c "BOARD_SIZE=5 LOAD_SOLUTION='$file' cargo run --quiet --bin solver"
+    [[ "$status" -ne 0 ]]
+}
+
+@test "csv and json exports list every action of the solved tables (_,_,_,_)" {
+    csv="$BATS_TEST_TMPDIR/solution.csv"
+    json="$BATS_TEST_TMPDIR/solution.json"
+    SOLVER_ENGINE="negamax" SAVE_CSV="$csv" SAVE_JSON="$json" cargo run --quiet --bin solver
+    [[ "$(head -n 1 "$csv")" = "state,player,action,result,plies,value,optimal" ]]
+    grep -qxF '"_,_,_,_",P1,(0),draw,,DRAW,false' "$csv"
+    grep -qxF '"_,_,_,_",P1,(1),win,3,WIN(3),true' "$csv"
+    [[ "$(grep -c '"state":' "$json")" -eq "$(($(wc -l < "$csv") - 1))" ]]
+    [[ "$(grep -c '"optimal": true' "$json")" -eq "$(grep -c ',true$' "$csv")" ]]
+}
+
+@test "csv export fails with the trainer engine" {
+    run bash -c "SOLVER_ENGINE=trainer SAVE_CSV='$BATS_TEST_TMPDIR/solution.csv' cargo run --quiet --bin solver"
+    [[ "$status" -ne 0 ]]
+}
+
+@test "comparisons and queries fail with the trainer engine" {
+    run bash -c "SOLVER_ENGINE=trainer COMPARE_QLEARNING=1 cargo run --quiet --bin solver"
+    [[ "$status" -ne 0 ]]
+    [[ "$output" = *"only exact under optimal play"* ]]
+    run bash -c "SOLVER_ENGINE=trainer COMPARE_MCTS=1 cargo run --quiet --bin solver"
+    [[ "$status" -ne 0 ]]
+    run bash -c "SOLVER_ENGINE=trainer cargo run --quiet --bin query -- 1,_,2,_ 2"
+    [[ "$status" -ne 0 ]]
+}
+
+@test "dot graph under optimal play (_,_,_,_)" {
+    file="$BATS_TEST_TMPDIR/solution.dot"
+    SAVE_DOT="$file" DOT_OPTIMAL_ONLY="1" cargo run --quiet --bin solver
+    [[ "$(head -n 1 "$file")" = "digraph {" ]]
+    [[ "$(grep -c -- "->" "$file")" -eq 16 ]]
+    grep -qF '0 [label="P1 _,_,_,_\n[WIN(3)]"];' "$file"
+    grep -qF '9 [label="2,1,1,_\nP1 wins", shape=ellipse];' "$file"
+    grep -qF '0 -> 1 [label="(1)", color=red, penwidth=2];' "$file"
+}
+
+@test "dot graph of every reachable position highlights the optimal actions" {
+    file="$BATS_TEST_TMPDIR/solution.dot"
+    SAVE_DOT="$file" cargo run --quiet --bin solver
+    grep -qF '0 -> 1 [label="(0)"];' "$file"
+    [[ "$(grep -c -- "penwidth" "$file")" -lt "$(grep -c -- "->" "$file")" ]]
+}
+
+@test "saved solution cannot be loaded from a different start position" {
+    file="$BATS_TEST_TMPDIR/solution.bin"
+    SAVE_SOLUTION="$file" cargo run --quiet --bin solver
+    run bash -c "START_POSITION='1,_,_,_' START_PLAYER='2' LOAD_SOLUTION='$file' cargo run --quiet --bin solver"
+    [[ "$status" -ne 0 ]]
+}
+
+@test "gap solver agrees with negamax with 2 stones per turn (_,_,_,_,_,_)" {
+    export BOARD_SIZE="6" TURN_SCHEDULE="1,2"
+    negamax="$(SOLVER_ENGINE="negamax" initial_state_line "_,_,_,_,_,_ +1")"
+    gaps="$(SOLVER_ENGINE="gaps" initial_state_line "_,_,_,_,_,_ +1")"
+    [[ "$gaps" = "$negamax" ]]
+}
+
+@test "gap solver solves a board of 300 cells" {
+    export BOARD_SIZE="300"
+    line="$(SOLVER_ENGINE="gaps" cargo run --quiet --bin solver | sed -n '/^result:/,$p' | grep -m 1 " \[")"
+    [[ "$line" = *" [WIN(3)] -> {(1), "*" (298)}" ]]
+}
+
+@test "negamax encodes a board of 40 cells like the gap solver" {
+    export BOARD_SIZE="40"
+    position="1,2,1,2,1,2,1,2,1,2,1,2,1,2,1,2,1,2,1,2,1,2,1,2,1,2,1,2,1,2,1,2,1,2,_,_,_,_,_,_"
+    negamax="$(SOLVER_ENGINE="negamax" cargo run --quiet --bin query -- "$position")"
+    gaps="$(SOLVER_ENGINE="gaps" cargo run --quiet --bin query -- "$position")"
+    [[ "$negamax" = "$gaps" ]]
+    [[ "$negamax" = *"best: (34)"* ]]
+}
+
+@test "negamax fails on a board too large to be encoded" {
+    run bash -c "BOARD_SIZE=300 SOLVER_ENGINE=negamax cargo run --quiet --bin solver"
+    [[ "$status" -ne 0 ]]
+    [[ "$output" = *"the board is too large to be encoded"* ]]
+}
+
+@test "quiet solver only prints the solved tables" {
+    run bash -c "SOLVER_LOG=off cargo run --quiet --bin solver 2>&1"
+    [[ "$status" -eq 0 ]]
+    [[ "${lines[0]}" = "result:" ]]
+}
+
+@test "trainer fails if not converged within the maximum epochs" {
+    run bash -c "BOARD_SIZE=6 SOLVER_MAX_EPOCHS=2 cargo run --quiet --bin solver"
+    [[ "$status" -ne 0 ]]
+    [[ "$output" = *"did not converge within 2 epochs"* ]]
+}
+
+@test "mcts picks optimal actions on a small board (_,_,_,_)" {
+    export SOLVER_ENGINE="negamax" COMPARE_MCTS="1" MCTS_ITERATIONS="200"
+    run bash -c "cargo run --quiet --bin solver | sed -n '/^mcts/,/^result:/p'"
+    [[ "${lines[1]}" = "12 states, optimal in 12 (100.0%), the same outcome in 12 (100.0%)" ]]
+}
+
+@test "mcts with the same seed searches the same" {
+    export BOARD_SIZE="7" TURN_SCHEDULE="1,2" SOLVER_ENGINE="negamax" COMPARE_MCTS="1" MCTS_ITERATIONS="20" MCTS_SEED="1"
+    first="$(cargo run --quiet --bin solver | sed -n '/^mcts/,/^result:/p')"
+    second="$(cargo run --quiet --bin solver | sed -n '/^mcts/,/^result:/p')"
+    [[ "$second" = "$first" ]]
+}
+
+@test "q-learning learns the optimal policy on a small board (_,_,_,_)" {
+    export SOLVER_ENGINE="negamax" COMPARE_QLEARNING="1"
+    run bash -c "cargo run --quiet --bin solver | sed -n '/^q-learning/,/^result:/p'"
+    [[ "${lines[1]}" = "12 states, optimal in 12 (100.0%), the same outcome in 12 (100.0%)" ]]
+}
+
+@test "perfect bots win every game as player 1 against each other (_,_,_,_)" {
+    run bash -c "SOLVER_LOG=off TOURNAMENT_BOTS=solver,negamax cargo run --quiet --bin tournament"
+    [[ "$status" -eq 0 ]]
+    [[ "${lines[2]}" = "solver         -  10-0-10" ]]
+    [[ "${lines[3]}" = "negamax  10-0-10        -" ]]
+}
+
+@test "tournament with the same seed plays the same games" {
+    export BOARD_SIZE="7" TOURNAMENT_BOTS="random,greedy,depth:2,mcts:20" TOURNAMENT_SEED="1" SOLVER_LOG="off"
+    first="$(cargo run --quiet --bin tournament)"
+    second="$(cargo run --quiet --bin tournament)"
+    [[ "$second" = "$first" ]]
+}
+
+@test "principal variation with alternatives (_,_,_,_)" {
+    run bash -c "SOLVER_LOG=off PRINCIPAL_VARIATION=1 cargo run --quiet --bin solver | sed -n '/^principal variation:/,/^result:/p'"
+    [[ "${lines[1]}" = "P1 _,_,_,_ [WIN(3)] -> (1) or (2)" ]]
+    [[ "${lines[2]}" = "P2 _,1,_,_ [LOSE(2)] -> (0) or (2) or (3)" ]]
+    [[ "${lines[3]}" = "P1 2,1,_,_ [WIN(1)] -> (2)" ]]
+    [[ "${lines[4]}" = "P1 wins" ]]
+}
+
+@test "gaps prints the same principal variation as negamax" {
+    export BOARD_SIZE="9" TURN_SCHEDULE="1,2" PRINCIPAL_VARIATION="1" SOLVER_LOG="off"
+    negamax="$(SOLVER_ENGINE=negamax cargo run --quiet --bin solver | sed -n '/^principal variation:/,/^P[12] wins$\|^draw$/p')"
+    gaps="$(SOLVER_ENGINE=gaps cargo run --quiet --bin solver | sed -n '/^principal variation:/,/^P[12] wins$\|^draw$/p')"
+    [[ -n "$gaps" ]]
+    [[ "$gaps" = "$negamax" ]]
+}
+
+@test "query prints the principal variation of a position" {
+    run bash -c "SOLVER_LOG=off cargo run --quiet --bin query -- --pv 1,_,2,_ 2 | sed -n '/^principal variation:/,\$p'"
+    [[ "$status" -eq 0 ]]
+    [[ "${lines[1]}" = "P2 1,_,2,_ [WIN(1)] -> (1) or (3)" ]]
+    [[ "${lines[2]}" = "P2 wins" ]]
+}
+
+@test "query prints the same principal variation as the solver" {
+    export BOARD_SIZE="9" TURN_SCHEDULE="1,2" SOLVER_LOG="off"
+    solver="$(PRINCIPAL_VARIATION=1 cargo run --quiet --bin solver | sed -n '/^principal variation:/,/^P[12] wins$\|^draw$/p')"
+    query="$(cargo run --quiet --bin query -- --pv --moves | sed -n '/^principal variation:/,\$p')"
+    gaps="$(SOLVER_ENGINE=gaps cargo run --quiet --bin query -- --pv --moves | sed -n '/^principal variation:/,\$p')"
+    [[ -n "$query" ]]
+    [[ "$query" = "$solver" ]]
+    [[ "$gaps" = "$solver" ]]
+}
+
+@test "query fails to print a principal variation with a proof search" {
+    run bash -c "SOLVER_LOG=off PROOF_SEARCH=dfpn cargo run --quiet --bin query -- --pv --moves"
+    [[ "$status" -ne 0 ]]
+    [[ "$output" =~ "no principal variation" ]]
+}
+
+@test "query a position (1,_,2,_)" {
+    run bash -c "cargo run --quiet --bin query -- 1,_,2,_ 2"
+    [[ "$status" -eq 0 ]]
+    [[ "${lines[1]}" = "player: P2" ]]
+    [[ "${lines[2]}" = "value: WIN(1)" ]]
+    [[ "${lines[3]}" = "action (1): WIN(1)" ]]
+    [[ "${lines[4]}" = "action (3): WIN(1)" ]]
+    [[ "${lines[5]}" = "best: (1), (3)" ]]
+}
+
+@test "query a move list agrees with the gap solver (_,1,_,_)" {
+    negamax="$(SOLVER_ENGINE=negamax cargo run --quiet --bin query -- --moves 1)"
+    gaps="$(SOLVER_ENGINE=gaps cargo run --quiet --bin query -- --moves 1)"
+    [[ "$gaps" = "$negamax" ]]
+    [[ "$gaps" = *"value: LOSE(2)"* ]]
+}
+
+@test "query a position agrees with the move list reaching it with 2 stones per turn (1,_,_,_,_,2)" {
+    export BOARD_SIZE="6" TURN_SCHEDULE="1,2"
+    position="$(cargo run --quiet --bin query -- 1,_,_,_,_,2 2)"
+    moves="$(cargo run --quiet --bin query -- --moves 0 5)"
+    [[ "$position" = "$moves" ]]
+    [[ "$position" = *"position: 1,_,_,_,_,2 +1"* ]]
+    [[ "$position" = *"value: WIN(1)"* ]]
+    first="$(cargo run --quiet --bin query -- 1,_,_,_,_,_ 2)"
+    [[ "$first" = "$(cargo run --quiet --bin query -- --moves 0)" ]]
+    [[ "$first" = *"value: WIN(2)"* ]]
+}
+
+@test "query gives the value of every legal action with the default engine (_,2,_,1)" {
+    run bash -c "cargo run --quiet --bin query -- _,2,_,1 1"
+    [[ "$status" -eq 0 ]]
+    [[ "${lines[3]}" = "action (0): LOSE(2)" ]]
+    [[ "${lines[4]}" = "action (2): WIN(1)" ]]
+}
+
+@test "query fails on malformed input" {
+    run bash -c "cargo run --quiet --bin query -- 1,_,x,_"
+    [[ "$status" -ne 0 ]]
+    run bash -c "cargo run --quiet --bin query -- --moves 0 2 1"
+    [[ "$status" -ne 0 ]]
+    run bash -c "cargo run --quiet --bin query"
+    [[ "$status" -ne 0 ]]
+    run bash -c "BOARD_SIZE=6 TURN_SCHEDULE=1,2 cargo run --quiet --bin query -- 1,_,_,_,_,_ 1"
+    [[ "$status" -ne 0 ]]
+    [[ "$output" = *"P1 cannot be to act with 1 stones against 0"* ]]
+}
+
+@test "proof searches agree with negamax (1,_,_,_,_,_,_)" {
+    export BOARD_SIZE="7"
+    run bash -c "cargo run --quiet --bin query -- 1,_,_,_,_,_,_ 2"
+    [[ "${lines[2]}" = "value: DRAW" ]]
+    run bash -c "PROOF_SEARCH=pns cargo run --quiet --bin query -- 1,_,_,_,_,_,_ 2"
+    [[ "${lines[2]}" = "proof: NO WIN" ]]
+    run bash -c "PROOF_SEARCH=dfpn cargo run --quiet --bin query -- 1,_,_,_,_,_,_ 2"
+    [[ "${lines[2]}" = "proof: NO WIN" ]]
+}
+
+@test "df-pn proves a win on a board of 300 cells" {
+    export BOARD_SIZE="300" PROOF_SEARCH="dfpn"
+    run bash -c "cargo run --quiet --bin query -- --moves"
+    [[ "$status" -eq 0 ]]
+    [[ "${lines[2]}" = "proof: WIN" ]]
+}
+
+@test "proof search is unknown once out of nodes" {
+    export BOARD_SIZE="300" PROOF_SEARCH="dfpn" PROOF_MAX_NODES="1"
+    run bash -c "cargo run --quiet --bin query -- --moves"
+    [[ "${lines[2]}" = "proof: UNKNOWN" ]]
+    [[ "${lines[3]}" = "nodes: 1" ]]
+}